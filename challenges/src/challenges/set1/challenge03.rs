@@ -45,6 +45,7 @@ fn solve(hex_input: &str) -> Result<u8, Box<dyn std::error::Error>> {
     Ok(*best_key)
 }
 
+#[allow(clippy::match_overlapping_arm)]
 pub fn score(bytes: &[u8]) -> f32 {
     let mut score = 0.0;
     let mut printable_count = 0;
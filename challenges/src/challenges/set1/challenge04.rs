@@ -1,4 +1,4 @@
-use serialize::from_hex;
+use serialize::load;
 use xor::single_byte_xor;
 use super::challenge03::score;
 
@@ -11,8 +11,10 @@ pub struct DetectionResult {
 }
 
 pub fn run() -> bool {
-    let input = std::fs::read_to_string("data/set01/challenge04.txt")
-        .expect("Failed to read input file");
+    let input = match load::file_lines("data/set01/challenge04.txt") {
+        Ok(loaded) => loaded.data,
+        Err(_) => return false,
+    };
     static EXPECTED: &str = "Now that the party is jumping\n";
 
     match solve(&input) {
@@ -24,19 +26,14 @@ pub fn run() -> bool {
     }
 }
 
-fn solve(file_input: &str) -> Option<DetectionResult> {
+fn solve(lines: &[Vec<u8>]) -> Option<DetectionResult> {
     let mut best_result: Option<DetectionResult> = None;
 
-    for (line_number, line) in file_input.lines().enumerate() {
-        let bytes = match from_hex(line) {
-            Ok(b) => b,
-            Err(_) => continue, // Skip malformed lines
-        };
-
+    for (line_number, bytes) in lines.iter().enumerate() {
         // Find best key for this line
         let line_result = (0u8..=255)
             .map(|key| {
-                let decrypted = single_byte_xor(&bytes, key);
+                let decrypted = single_byte_xor(bytes, key);
                 let line_score = score(&decrypted);
                 DetectionResult {
                     key,
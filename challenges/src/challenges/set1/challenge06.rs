@@ -1,10 +1,10 @@
-use serialize::from_base64_file;
+use serialize::load;
 use xor::{repeating_key_xor, single_byte_xor};
 use super::challenge03::score;
 
 pub fn run() -> bool {
-    let input = match from_base64_file("data/set01/challenge06.txt") {
-        Ok(content) => content,
+    let input = match load::file("data/set01/challenge06.txt") {
+        Ok(loaded) => loaded.data,
         Err(_) => return false,
     };
     static EXPECTED: &str = "Terminator X: Bring the noise";
@@ -32,10 +32,10 @@ fn solve(ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     // Try each keysize and find best size overall
     candidates
         .into_iter()
-        .map(|keysize| break_repeating_xor(&ciphertext, keysize))
+        .map(|keysize| break_repeating_xor(ciphertext, keysize))
         .max_by(|a, b| {
-            let score_a = score(&repeating_key_xor(&ciphertext, &a));
-            let score_b = score(&repeating_key_xor(&ciphertext, &b));
+            let score_a = score(&repeating_key_xor(ciphertext, a));
+            let score_b = score(&repeating_key_xor(ciphertext, b));
             score_a.partial_cmp(&score_b).unwrap_or(std::cmp::Ordering::Equal)
         })
         .ok_or_else(|| "No valid key found".into())
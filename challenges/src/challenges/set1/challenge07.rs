@@ -1,9 +1,9 @@
 use crypto::{aes128_decrypt, AesError};
-use serialize::from_base64_file;
+use serialize::load;
 
 pub fn run() -> bool {
-    let input = match from_base64_file("data/set01/challenge07.txt") {
-        Ok(content) => content,
+    let input = match load::file("data/set01/challenge07.txt") {
+        Ok(loaded) => loaded.data,
         Err(_) => return false,
     };
     static KEY: &[u8] = b"YELLOW SUBMARINE";
@@ -24,6 +24,6 @@ pub fn run() -> bool {
 }
 
 fn solve(ciphertext: &[u8], key: &[u8]) -> Result<Vec<u8>, AesError> {
-    let plaintext = aes128_decrypt(&ciphertext, key, crypto::AesMode::ECB)?;
+    let plaintext = aes128_decrypt(ciphertext, key, crypto::AesMode::ECB)?;
     Ok(plaintext)
 }
\ No newline at end of file
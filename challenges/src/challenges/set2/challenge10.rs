@@ -1,9 +1,10 @@
-use serialize::from_base64_file;
+use serialize::load;
 use crypto::{aes128_decrypt, AesError, AesMode};
 
 pub fn run() -> bool {
-    let input = from_base64_file("data/set02/challenge10.txt")  
-        .expect("Failed to read file");
+    let input = load::file("data/set02/challenge10.txt")
+        .expect("Failed to read file")
+        .data;
     static KEY: &[u8] = b"YELLOW SUBMARINE";
     static IV: &[u8; 16] = &[0u8; 16];
     static EXPECTED: &str = "I'm back and I'm ringin' the bell \n";
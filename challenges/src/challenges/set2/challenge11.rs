@@ -19,6 +19,7 @@ pub fn run() -> bool {
 }
 
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum DetectedMode {
     ECB,
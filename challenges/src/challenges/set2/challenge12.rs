@@ -1,4 +1,4 @@
-use crypto::{aes::{block, pkcs7_pad, AES128_BLOCK_SIZE}, aes128_encrypt};
+use crypto::{aes::{pkcs7_pad, AES128_BLOCK_SIZE}, aes128_encrypt};
 use serialize::from_base64;
 
 use std::collections::{HashSet, HashMap};
@@ -24,20 +24,20 @@ fn main() {
         "0" => run_all_challenges_quiet(),
 
         // Set 1
-        "1" => run_challenge("Set 1, Challenge 01", || set1::challenge01::run()),
-        "2" => run_challenge("Set 1, Challenge 02", || set1::challenge02::run()),
-        "3" => run_challenge("Set 1, Challenge 03", || set1::challenge03::run()),
-        "4" => run_challenge("Set 1, Challenge 04", || set1::challenge04::run()),
-        "5" => run_challenge("Set 1, Challenge 05", || set1::challenge05::run()),
-        "6" => run_challenge("Set 1, Challenge 06", || set1::challenge06::run()),
-        "7" => run_challenge("Set 1, Challenge 07", || set1::challenge07::run()),
-        "8" => run_challenge("Set 1, Challenge 08", || set1::challenge08::run()),
+        "1" => run_challenge("Set 1, Challenge 01", set1::challenge01::run),
+        "2" => run_challenge("Set 1, Challenge 02", set1::challenge02::run),
+        "3" => run_challenge("Set 1, Challenge 03", set1::challenge03::run),
+        "4" => run_challenge("Set 1, Challenge 04", set1::challenge04::run),
+        "5" => run_challenge("Set 1, Challenge 05", set1::challenge05::run),
+        "6" => run_challenge("Set 1, Challenge 06", set1::challenge06::run),
+        "7" => run_challenge("Set 1, Challenge 07", set1::challenge07::run),
+        "8" => run_challenge("Set 1, Challenge 08", set1::challenge08::run),
 
         // Set 2
-        "9" => run_challenge("Set 2, Challenge 09",  || set2::challenge09::run()),
-        "10" => run_challenge("Set 2, Challenge 10", || set2::challenge10::run()),
-        "11" => run_challenge("Set 2, Challenge 11", || set2::challenge11::run()),
-        "12" => run_challenge("Set 2, Challenge 12", || set2::challenge12::run()),
+        "9" => run_challenge("Set 2, Challenge 09",  set2::challenge09::run),
+        "10" => run_challenge("Set 2, Challenge 10", set2::challenge10::run),
+        "11" => run_challenge("Set 2, Challenge 11", set2::challenge11::run),
+        "12" => run_challenge("Set 2, Challenge 12", set2::challenge12::run),
 
         _ => {
             eprintln!("Unknown challenge: Challenge {}", challenge);
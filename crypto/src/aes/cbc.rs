@@ -6,7 +6,7 @@ use xor::fixed_xor;
 const AES128_BLOCK_SIZE: usize = 16;
 
 pub fn encrypt(plaintext: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>, AesError> {
-    if plaintext.len() % AES128_BLOCK_SIZE != 0 {
+    if !plaintext.len().is_multiple_of(AES128_BLOCK_SIZE) {
         return Err(AesError::InvalidBlockSize);
     }
 
@@ -27,7 +27,7 @@ pub fn encrypt(plaintext: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>, AesEr
 }
 
 pub fn decrypt(ciphertext: &[u8], key: &[u8], iv: &[u8]) -> Result<Vec<u8>, AesError> {
-    if ciphertext.len() % AES128_BLOCK_SIZE != 0 {
+    if !ciphertext.len().is_multiple_of(AES128_BLOCK_SIZE) {
         return Err(AesError::InvalidBlockSize);
     }
 
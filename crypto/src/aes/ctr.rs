@@ -0,0 +1,71 @@
+// crypto/src/aes/ctr.rs
+use crate::common::AesError;
+use super::block::encrypt_block;
+
+const AES128_BLOCK_SIZE: usize = 16;
+
+/// CTR keystream blocks are E(nonce || counter), both as 64-bit little-endian
+/// integers. Encryption and decryption are the same operation.
+pub fn apply(data: &[u8], key: &[u8], nonce: u64) -> Result<Vec<u8>, AesError> {
+    let mut result = Vec::with_capacity(data.len());
+
+    for (counter, chunk) in data.chunks(AES128_BLOCK_SIZE).enumerate() {
+        let mut counter_block = [0u8; AES128_BLOCK_SIZE];
+        counter_block[..8].copy_from_slice(&nonce.to_le_bytes());
+        counter_block[8..].copy_from_slice(&(counter as u64).to_le_bytes());
+
+        let keystream = encrypt_block(&counter_block, key)?;
+        result.extend(chunk.iter().zip(keystream).map(|(byte, k)| byte ^ k));
+    }
+
+    Ok(result)
+}
+
+/// CTR as SP 800-38A writes it: keystream blocks are E(T_i), where T_1 is
+/// `counter_block` and each T_i after it adds one as a 128-bit big-endian
+/// integer. This is the layout of the NIST test vectors.
+pub fn apply_big_endian(data: &[u8], key: &[u8], counter_block: &[u8; AES128_BLOCK_SIZE]) -> Result<Vec<u8>, AesError> {
+    let mut counter = u128::from_be_bytes(*counter_block);
+    let mut result = Vec::with_capacity(data.len());
+
+    for chunk in data.chunks(AES128_BLOCK_SIZE) {
+        let keystream = encrypt_block(&counter.to_be_bytes(), key)?;
+        result.extend(chunk.iter().zip(keystream).map(|(byte, k)| byte ^ k));
+        counter = counter.wrapping_add(1);
+    }
+
+    Ok(result)
+}
+
+pub fn encrypt(plaintext: &[u8], key: &[u8], nonce: u64) -> Result<Vec<u8>, AesError> {
+    apply(plaintext, key, nonce)
+}
+
+pub fn decrypt(ciphertext: &[u8], key: &[u8], nonce: u64) -> Result<Vec<u8>, AesError> {
+    apply(ciphertext, key, nonce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serialize::from_base64;
+
+    #[test]
+    fn test_cryptopals_18() {
+        let ciphertext = from_base64("L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==").unwrap();
+        let plaintext = decrypt(&ciphertext, b"YELLOW SUBMARINE", 0).unwrap();
+        assert_eq!(plaintext, b"Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby ");
+    }
+
+    #[test]
+    fn test_big_endian_counter_carries() {
+        let key = b"YELLOW SUBMARINE";
+        let mut counter_block = [0u8; AES128_BLOCK_SIZE];
+        counter_block[8..].fill(0xff);
+
+        let keystream = apply_big_endian(&[0; 2 * AES128_BLOCK_SIZE], key, &counter_block).unwrap();
+        let mut carried = [0u8; AES128_BLOCK_SIZE];
+        carried[7] = 1;
+        assert_eq!(keystream[16..], encrypt_block(&carried, key).unwrap());
+    }
+}
@@ -0,0 +1,122 @@
+// crypto/src/aes/gcm.rs
+use crate::common::AesError;
+use crate::gf2_128::Gf128;
+use super::block::encrypt_block;
+
+const AES128_BLOCK_SIZE: usize = 16;
+pub const GCM_NONCE_SIZE: usize = 12;
+pub const GCM_TAG_SIZE: usize = 16;
+
+/// The blocks GHASH absorbs: AAD and ciphertext, each zero-padded to a block
+/// boundary, then their bit lengths as two 64-bit big-endian integers
+pub fn ghash_blocks(aad: &[u8], ciphertext: &[u8]) -> Vec<Gf128> {
+    let mut lengths = [0u8; AES128_BLOCK_SIZE];
+    lengths[..8].copy_from_slice(&(aad.len() as u64 * 8).to_be_bytes());
+    lengths[8..].copy_from_slice(&(ciphertext.len() as u64 * 8).to_be_bytes());
+
+    aad.chunks(AES128_BLOCK_SIZE)
+        .chain(ciphertext.chunks(AES128_BLOCK_SIZE))
+        .chain(std::iter::once(&lengths[..]))
+        .map(Gf128::from_block)
+        .collect()
+}
+
+/// GHASH_H: Horner evaluation of the blocks as a polynomial in H
+pub fn ghash(h: Gf128, aad: &[u8], ciphertext: &[u8]) -> Gf128 {
+    ghash_blocks(aad, ciphertext).into_iter().fold(Gf128::ZERO, |acc, block| (acc + block) * h)
+}
+
+/// The pre-counter block J0 for a 96-bit nonce: nonce || 0^31 || 1
+fn initial_counter(nonce: &[u8; GCM_NONCE_SIZE]) -> [u8; AES128_BLOCK_SIZE] {
+    let mut counter = [0u8; AES128_BLOCK_SIZE];
+    counter[..GCM_NONCE_SIZE].copy_from_slice(nonce);
+    counter[15] = 1;
+    counter
+}
+
+/// GCTR: CTR mode incrementing only the low 32 bits of the counter block
+fn gctr(key: &[u8], start: &[u8; AES128_BLOCK_SIZE], data: &[u8]) -> Result<Vec<u8>, AesError> {
+    let mut counter = *start;
+    let mut result = Vec::with_capacity(data.len());
+
+    for chunk in data.chunks(AES128_BLOCK_SIZE) {
+        let low = u32::from_be_bytes([counter[12], counter[13], counter[14], counter[15]]).wrapping_add(1);
+        counter[12..].copy_from_slice(&low.to_be_bytes());
+
+        let keystream = encrypt_block(&counter, key)?;
+        result.extend(chunk.iter().zip(keystream).map(|(byte, k)| byte ^ k));
+    }
+
+    Ok(result)
+}
+
+/// The hash subkey H = E_K(0^128)
+pub fn auth_key(key: &[u8]) -> Result<Gf128, AesError> {
+    Ok(Gf128::from_block(&encrypt_block(&[0u8; AES128_BLOCK_SIZE], key)?))
+}
+
+fn compute_tag(key: &[u8], nonce: &[u8; GCM_NONCE_SIZE], aad: &[u8], ciphertext: &[u8]) -> Result<[u8; GCM_TAG_SIZE], AesError> {
+    let mask = Gf128::from_block(&encrypt_block(&initial_counter(nonce), key)?);
+    Ok((ghash(auth_key(key)?, aad, ciphertext) + mask).to_block())
+}
+
+/// Encrypts and authenticates, returning `ciphertext || tag`
+pub fn seal(plaintext: &[u8], key: &[u8], nonce: &[u8; GCM_NONCE_SIZE], aad: &[u8]) -> Result<Vec<u8>, AesError> {
+    let mut ciphertext = gctr(key, &initial_counter(nonce), plaintext)?;
+    let tag = compute_tag(key, nonce, aad, &ciphertext)?;
+    ciphertext.extend_from_slice(&tag);
+    Ok(ciphertext)
+}
+
+/// Checks the tag before decrypting anything
+pub fn open(sealed: &[u8], key: &[u8], nonce: &[u8; GCM_NONCE_SIZE], aad: &[u8]) -> Result<Vec<u8>, AesError> {
+    if sealed.len() < GCM_TAG_SIZE {
+        return Err(AesError::AuthenticationFailed);
+    }
+    let (ciphertext, tag) = sealed.split_at(sealed.len() - GCM_TAG_SIZE);
+
+    let expected = compute_tag(key, nonce, aad, ciphertext)?;
+    let difference = expected.iter().zip(tag).fold(0, |diff, (a, b)| diff | (a ^ b));
+    if difference != 0 {
+        return Err(AesError::AuthenticationFailed);
+    }
+
+    gctr(key, &initial_counter(nonce), ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serialize::{from_hex, to_hex};
+
+    // Test case 4 from the original GCM specification (McGrew & Viega)
+    const KEY: &str = "feffe9928665731c6d6a8f9467308308";
+    const NONCE: &str = "cafebabefacedbaddecaf888";
+    const PLAINTEXT: &str = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39";
+    const AAD: &str = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+    const CIPHERTEXT: &str = "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091";
+    const TAG: &str = "5bc94fbc3221a5db94fae95ae7121a47";
+
+    fn nonce() -> [u8; GCM_NONCE_SIZE] {
+        from_hex(NONCE).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_seal_vector() {
+        let sealed = seal(&from_hex(PLAINTEXT).unwrap(), &from_hex(KEY).unwrap(), &nonce(), &from_hex(AAD).unwrap()).unwrap();
+        assert_eq!(to_hex(&sealed), format!("{}{}", CIPHERTEXT, TAG));
+    }
+
+    #[test]
+    fn test_open_rejects_tampering() {
+        let key = from_hex(KEY).unwrap();
+        let aad = from_hex(AAD).unwrap();
+        let sealed = from_hex(&format!("{}{}", CIPHERTEXT, TAG)).unwrap();
+        assert_eq!(open(&sealed, &key, &nonce(), &aad).unwrap(), from_hex(PLAINTEXT).unwrap());
+
+        let mut tampered = sealed.clone();
+        tampered[0] ^= 1;
+        assert!(matches!(open(&tampered, &key, &nonce(), &aad), Err(AesError::AuthenticationFailed)));
+        assert!(matches!(open(&sealed, &key, &nonce(), b"other aad"), Err(AesError::AuthenticationFailed)));
+    }
+}
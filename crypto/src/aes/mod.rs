@@ -2,6 +2,9 @@
 pub mod block;
 pub mod ecb;
 pub mod cbc;
+pub mod ctr;
+pub mod gcm;
+pub mod nonce_reuse;
 pub mod padding;
 
 use crate::common::AesError;
@@ -14,6 +17,9 @@ pub const AES128_BLOCK_SIZE: usize = 16;
 pub enum AesMode {
     ECB,
     CBC { iv: [u8; AES128_BLOCK_SIZE] },
+    CTR { nonce: u64 },
+    /// Sealed output is `ciphertext || tag`, with no associated data
+    GCM { nonce: [u8; gcm::GCM_NONCE_SIZE] },
 }
 
 /// Main public AES-128 encrypt API
@@ -21,6 +27,8 @@ pub fn aes128_encrypt(plaintext: &[u8], key: &[u8], mode: AesMode) -> Result<Vec
     match mode {
         AesMode::ECB => ecb::encrypt(plaintext, key),
         AesMode::CBC { iv } => cbc::encrypt(plaintext, key, &iv),
+        AesMode::CTR { nonce } => ctr::encrypt(plaintext, key, nonce),
+        AesMode::GCM { nonce } => gcm::seal(plaintext, key, &nonce, &[]),
     }
 }
 
@@ -29,5 +37,36 @@ pub fn aes128_decrypt(ciphertext: &[u8], key: &[u8], mode: AesMode) -> Result<Ve
     match mode {
         AesMode::ECB => ecb::decrypt(ciphertext, key),
         AesMode::CBC { iv } => cbc::decrypt(ciphertext, key, &iv),
+        AesMode::CTR { nonce } => ctr::decrypt(ciphertext, key, nonce),
+        AesMode::GCM { nonce } => gcm::open(ciphertext, key, &nonce, &[]),
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn prop_ecb_cbc_inverse(
+            plaintext in prop::collection::vec(any::<u8>(), 0..100),
+            key: [u8; 16],
+            iv: [u8; 16],
+        ) {
+            let padded = pkcs7_pad(&plaintext, AES128_BLOCK_SIZE);
+            for mode in [AesMode::ECB, AesMode::CBC { iv }] {
+                let ciphertext = aes128_encrypt(&padded, &key, mode.clone()).unwrap();
+                prop_assert_eq!(ciphertext.len(), padded.len());
+                let decrypted = aes128_decrypt(&ciphertext, &key, mode).unwrap();
+                prop_assert_eq!(pkcs7_unpad(&decrypted).unwrap(), plaintext.as_slice());
+            }
+        }
+
+        #[test]
+        fn prop_ctr_inverse(plaintext in prop::collection::vec(any::<u8>(), 0..100), key: [u8; 16], nonce: u64) {
+            let ciphertext = aes128_encrypt(&plaintext, &key, AesMode::CTR { nonce }).unwrap();
+            prop_assert_eq!(aes128_decrypt(&ciphertext, &key, AesMode::CTR { nonce }).unwrap(), plaintext);
+        }
+    }
+}
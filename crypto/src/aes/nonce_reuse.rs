@@ -0,0 +1,103 @@
+// crypto/src/aes/nonce_reuse.rs
+use crate::gf2_128::{Gf128, Polynomial};
+use super::gcm::{ghash, ghash_blocks, GCM_TAG_SIZE};
+
+/// A GCM message as an eavesdropper sees it
+#[derive(Debug, Clone, PartialEq)]
+pub struct GcmMessage {
+    pub aad: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+    pub tag: Gf128,
+}
+
+impl GcmMessage {
+    /// Splits `ciphertext || tag` as produced by `gcm::seal`
+    pub fn from_sealed(aad: &[u8], sealed: &[u8]) -> Option<Self> {
+        let split = sealed.len().checked_sub(GCM_TAG_SIZE)?;
+        Some(Self {
+            aad: aad.to_vec(),
+            ciphertext: sealed[..split].to_vec(),
+            tag: Gf128::from_block(&sealed[split..]),
+        })
+    }
+
+    pub fn to_sealed(&self) -> Vec<u8> {
+        [&self.ciphertext[..], &self.tag.to_block()].concat()
+    }
+}
+
+/// X_1 x^N + ... + X_N x + tag. Every message under one (key, nonce) pair
+/// evaluates to the same mask E_K(J0) at x = H.
+pub fn tag_polynomial(message: &GcmMessage) -> Polynomial {
+    let mut coeffs = vec![message.tag];
+    coeffs.extend(ghash_blocks(&message.aad, &message.ciphertext).into_iter().rev());
+    Polynomial::new(coeffs)
+}
+
+/// The "forbidden attack": with the nonce repeated, the difference of two tag
+/// polynomials vanishes at H, so H is one of its roots. Each further message
+/// under the same nonce narrows the candidates down.
+pub fn recover_auth_key(messages: &[GcmMessage]) -> Vec<Gf128> {
+    let Some((first, rest)) = messages.split_first() else {
+        return Vec::new();
+    };
+    let Some(second) = rest.first() else {
+        return Vec::new();
+    };
+
+    let reference = tag_polynomial(first);
+    let mut candidates = (&reference + &tag_polynomial(second)).roots();
+
+    for message in &rest[1..] {
+        let difference = &reference + &tag_polynomial(message);
+        candidates.retain(|&h| difference.eval(h).is_zero());
+    }
+
+    candidates
+}
+
+/// Once H is known, the per-nonce mask is tag - GHASH_H of any captured
+/// message, and a valid tag can be produced for arbitrary AAD and ciphertext
+pub fn forge(h: Gf128, known: &GcmMessage, aad: &[u8], ciphertext: &[u8]) -> GcmMessage {
+    let mask = known.tag + ghash(h, &known.aad, &known.ciphertext);
+    GcmMessage {
+        aad: aad.to_vec(),
+        ciphertext: ciphertext.to_vec(),
+        tag: ghash(h, aad, ciphertext) + mask,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aes::gcm::{auth_key, open, seal};
+    use crate::utils::oracle::random_aes_key;
+
+    #[test]
+    fn test_recover_auth_key() {
+        let key = random_aes_key();
+        let nonce = [7u8; 12];
+
+        let plaintexts: [(&[u8], &[u8]); 3] = [
+            (b"attack at dawn, bring the good cheese", b"header-1"),
+            (b"retreat at dusk", b"header-2 is a little longer"),
+            (b"hold position until further notice, again", b""),
+        ];
+        let messages: Vec<GcmMessage> = plaintexts.iter()
+            .map(|(plaintext, aad)| GcmMessage::from_sealed(aad, &seal(plaintext, &key, &nonce, aad).unwrap()).unwrap())
+            .collect();
+
+        let candidates = recover_auth_key(&messages);
+        assert_eq!(candidates, vec![auth_key(&key).unwrap()]);
+
+        // Flip "dawn" to "dusk" in the first message and re-tag it
+        let mut ciphertext = messages[0].ciphertext.clone();
+        for (c, (a, b)) in ciphertext[10..14].iter_mut().zip(b"dawn".iter().zip(b"dusk")) {
+            *c ^= a ^ b;
+        }
+        let forged = forge(candidates[0], &messages[0], b"forged header", &ciphertext);
+
+        let opened = open(&forged.to_sealed(), &key, &nonce, b"forged header").unwrap();
+        assert_eq!(opened, b"attack at dusk, bring the good cheese");
+    }
+}
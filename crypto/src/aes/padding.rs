@@ -1,6 +1,9 @@
 use super::AesError;
 
+/// Panics unless `block_size` fits in the pad byte, i.e. is 1 to 255
 pub fn pkcs7_pad(data: &[u8], block_size: usize) -> Vec<u8> {
+    assert!((1..=255).contains(&block_size), "PKCS#7 block size must be 1 to 255, not {}", block_size);
+
     let padding_len = data.len() % block_size;
     let padding_req = if padding_len == 0 {
         block_size
@@ -37,4 +40,39 @@ pub fn pkcs7_unpad(data: &[u8]) -> Result<Vec<u8>, AesError> {
     }
 
     Ok(data[..padding_start].to_vec())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Used to emit a zero pad byte, which nothing could unpad
+    #[test]
+    #[should_panic(expected = "must be 1 to 255")]
+    fn test_pad_rejects_block_size_256() {
+        pkcs7_pad(&[], 256);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_pad_round_trip(data in prop::collection::vec(any::<u8>(), 0..600), block_size in 1usize..=1024) {
+            if block_size > 255 {
+                prop_assert!(std::panic::catch_unwind(|| pkcs7_pad(&data, block_size)).is_err());
+                return Ok(());
+            }
+
+            let padded = pkcs7_pad(&data, block_size);
+            prop_assert!(padded.len().is_multiple_of(block_size));
+            prop_assert!(padded.len() > data.len() && padded.len() <= data.len() + block_size);
+            prop_assert_eq!(pkcs7_unpad(&padded).unwrap(), data);
+        }
+
+        #[test]
+        fn prop_unpad_never_panics(data in prop::collection::vec(any::<u8>(), 0..64)) {
+            if let Ok(unpadded) = pkcs7_unpad(&data) {
+                prop_assert!(unpadded.len() < data.len());
+            }
+        }
+    }
+}
@@ -0,0 +1,271 @@
+// crypto/src/bigint/mod.rs
+pub mod prime;
+
+pub use num_bigint::{BigInt, BigUint, Sign};
+pub use num_integer::Integer;
+pub use num_traits::{One, Zero};
+
+pub use prime::{is_probable_prime, random_below, random_bits, random_prime, random_range};
+
+use serialize::{Der, SerializeError};
+
+/// base^exp mod modulus
+pub fn modexp(base: &BigUint, exp: &BigUint, modulus: &BigUint) -> BigUint {
+    base.modpow(exp, modulus)
+}
+
+/// (a - b) mod modulus without going negative
+pub fn mod_sub(a: &BigUint, b: &BigUint, modulus: &BigUint) -> BigUint {
+    let a = a % modulus;
+    let b = b % modulus;
+
+    if a >= b {
+        a - b
+    } else {
+        modulus - (b - a)
+    }
+}
+
+/// Returns (g, x, y) with a*x + b*y = g = gcd(a, b)
+pub fn extended_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let (mut old_r, mut r) = (a.clone(), b.clone());
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+    let (mut old_t, mut t) = (BigInt::zero(), BigInt::one());
+
+    while !r.is_zero() {
+        let quotient = &old_r / &r;
+
+        let next_r = &old_r - &quotient * &r;
+        old_r = std::mem::replace(&mut r, next_r);
+        let next_s = &old_s - &quotient * &s;
+        old_s = std::mem::replace(&mut s, next_s);
+        let next_t = &old_t - &quotient * &t;
+        old_t = std::mem::replace(&mut t, next_t);
+    }
+
+    (old_r, old_s, old_t)
+}
+
+/// Inverse of `a` modulo `modulus`, if gcd(a, modulus) = 1
+pub fn modinv(a: &BigUint, modulus: &BigUint) -> Option<BigUint> {
+    if modulus.is_zero() {
+        return None;
+    }
+
+    let a = BigInt::from(a % modulus);
+    let m = BigInt::from(modulus.clone());
+    let (g, x, _) = extended_gcd(&a, &m);
+
+    if !g.is_one() {
+        return None;
+    }
+
+    x.mod_floor(&m).to_biguint()
+}
+
+/// floor(n^(1/k))
+pub fn nth_root(n: &BigUint, k: u32) -> BigUint {
+    n.nth_root(k)
+}
+
+/// floor(n^(1/3))
+pub fn cube_root(n: &BigUint) -> BigUint {
+    n.cbrt()
+}
+
+/// The k-th root of `n` if `n` is a perfect k-th power
+pub fn exact_nth_root(n: &BigUint, k: u32) -> Option<BigUint> {
+    let root = n.nth_root(k);
+    if root.pow(k) == *n {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// Chinese remainder theorem over pairwise coprime moduli.
+/// Returns x mod M with x = residues[i] (mod moduli[i]), and M = prod(moduli).
+pub fn crt(residues: &[BigUint], moduli: &[BigUint]) -> Option<(BigUint, BigUint)> {
+    if residues.len() != moduli.len() || moduli.is_empty() {
+        return None;
+    }
+
+    let mut x = BigUint::zero();
+    let mut m = BigUint::one();
+
+    for (r, mi) in residues.iter().zip(moduli) {
+        // Lift x so it also satisfies x = r (mod mi)
+        let step = mod_sub(r, &x, mi) * modinv(&(&m % mi), mi)? % mi;
+        x += &m * step;
+        m *= mi;
+    }
+
+    Some((x, m))
+}
+
+/// Whether `a` is a nonzero square mod the odd prime `p` (Euler's criterion)
+pub fn is_quadratic_residue(a: &BigUint, p: &BigUint) -> bool {
+    let a = a % p;
+    !a.is_zero() && modexp(&a, &((p - 1u32) >> 1), p).is_one()
+}
+
+/// A square root of `a` mod the odd prime `p` by Tonelli–Shanks, if one exists.
+/// The other root is p minus this one.
+pub fn sqrt_mod(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let a = a % p;
+    if a.is_zero() {
+        return Some(a);
+    }
+    if !is_quadratic_residue(&a, p) {
+        return None;
+    }
+
+    // p - 1 = q * 2^s with q odd
+    let p_minus_one: BigUint = p - 1u32;
+    let s = p_minus_one.trailing_zeros().unwrap_or(0);
+    let q = &p_minus_one >> s;
+
+    let mut z = BigUint::from(2u32);
+    while is_quadratic_residue(&z, p) {
+        z += 1u32;
+    }
+
+    let mut m = s;
+    let mut c = modexp(&z, &q, p);
+    let mut t = modexp(&a, &q, p);
+    let mut r = modexp(&a, &((&q + 1u32) >> 1), p);
+
+    while !t.is_one() {
+        // Least i with t^(2^i) = 1
+        let mut i = 0;
+        let mut t_squared = t.clone();
+        while !t_squared.is_one() {
+            t_squared = &t_squared * &t_squared % p;
+            i += 1;
+        }
+
+        let b = modexp(&c, &(BigUint::one() << (m - i - 1)), p);
+        m = i;
+        c = &b * &b % p;
+        t = t * &c % p;
+        r = r * b % p;
+    }
+
+    Some(r)
+}
+
+/// Distinct primes below `bound` dividing n, by trial division
+pub fn small_factors(n: &BigUint, bound: u64) -> Vec<u64> {
+    let mut n = n.clone();
+    let mut factors = Vec::new();
+
+    for candidate in 2..bound {
+        if (&n % candidate).is_zero() {
+            factors.push(candidate);
+            while (&n % candidate).is_zero() {
+                n /= candidate;
+            }
+        }
+    }
+
+    factors
+}
+
+pub fn to_bytes(n: &BigUint) -> Vec<u8> {
+    n.to_bytes_be()
+}
+
+/// Big-endian bytes left-padded with zeros to `len` (longer if `n` doesn't fit)
+pub fn to_bytes_padded(n: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = n.to_bytes_be();
+    if bytes.len() >= len {
+        return bytes;
+    }
+
+    let mut padded = vec![0u8; len - bytes.len()];
+    padded.extend_from_slice(&bytes);
+    padded
+}
+
+pub fn from_bytes(bytes: &[u8]) -> BigUint {
+    BigUint::from_bytes_be(bytes)
+}
+
+pub fn to_hex(n: &BigUint) -> String {
+    serialize::to_hex(&n.to_bytes_be())
+}
+
+/// Parse a big-endian hex number, ignoring whitespace (as in RFC group listings)
+/// and allowing an odd number of digits
+pub fn from_hex(hex: &str) -> Result<BigUint, SerializeError> {
+    let mut digits: String = hex.chars().filter(|c| !c.is_whitespace()).collect();
+    if digits.len() % 2 == 1 {
+        digits.insert(0, '0');
+    }
+
+    Ok(BigUint::from_bytes_be(&serialize::from_hex(&digits)?))
+}
+
+/// A DER INTEGER holding `n`
+pub fn to_der(n: &BigUint) -> Der {
+    Der::unsigned(&n.to_bytes_be())
+}
+
+/// The value of a non-negative DER INTEGER
+pub fn from_der(der: &Der) -> Result<BigUint, SerializeError> {
+    Ok(BigUint::from_bytes_be(der.as_unsigned()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(n: u64) -> BigUint {
+        BigUint::from(n)
+    }
+
+    #[test]
+    fn test_modexp_and_modinv() {
+        assert_eq!(modexp(&big(4), &big(13), &big(497)), big(445));
+        assert_eq!(modinv(&big(17), &big(3120)), Some(big(2753)));
+        assert_eq!(modinv(&big(6), &big(9)), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        let (x, m) = crt(&[big(2), big(3), big(2)], &[big(3), big(5), big(7)]).unwrap();
+        assert_eq!((x, m), (big(23), big(105)));
+    }
+
+    #[test]
+    fn test_sqrt_mod() {
+        // 3 * 2^30 + 1, so Tonelli–Shanks has to walk a long 2-power chain
+        let p = big(3 * (1 << 30) + 1);
+        for a in [big(2), big(5), big(123456789)] {
+            match sqrt_mod(&a, &p) {
+                Some(root) => assert_eq!(&root * &root % &p, &a % &p),
+                None => assert!(!is_quadratic_residue(&a, &p)),
+            }
+        }
+        assert_eq!(sqrt_mod(&big(2), &big(7)).map(|r| &r * &r % 7u32), Some(big(2)));
+        assert!(sqrt_mod(&big(3), &big(7)).is_none());
+    }
+
+    #[test]
+    fn test_roots() {
+        let n = big(123456789).pow(3u32);
+        assert_eq!(cube_root(&n), big(123456789));
+        assert_eq!(cube_root(&(&n + 1u32)), big(123456789));
+        assert_eq!(exact_nth_root(&n, 3), Some(big(123456789)));
+        assert_eq!(exact_nth_root(&(&n + 1u32), 3), None);
+        assert_eq!(nth_root(&big(1u64 << 50), 5), big(1024));
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let n = from_hex("ffff ffff\nc90fdaa2 2").unwrap();
+        assert_eq!(to_hex(&n), "0ffffffffc90fdaa22");
+        assert_eq!(from_hex(&to_hex(&n)).unwrap(), n);
+        assert_eq!(to_bytes_padded(&big(1), 4), vec![0, 0, 0, 1]);
+    }
+}
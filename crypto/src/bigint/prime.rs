@@ -0,0 +1,128 @@
+// crypto/src/bigint/prime.rs
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use crate::utils::oracle::random_bytes;
+
+const SMALL_PRIMES: [u32; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+/// Miller-Rabin rounds used by `random_prime`, for an error rate below 2^-80
+const PRIME_ROUNDS: usize = 40;
+
+/// Uniformly random integer with at most `bits` bits
+pub fn random_bits(bits: u64) -> BigUint {
+    if bits == 0 {
+        return BigUint::zero();
+    }
+
+    let mut bytes = random_bytes(bits.div_ceil(8) as usize);
+    let excess = bytes.len() as u64 * 8 - bits;
+    bytes[0] &= 0xff >> excess;
+
+    BigUint::from_bytes_be(&bytes)
+}
+
+/// Uniformly random integer in [0, n)
+pub fn random_below(n: &BigUint) -> BigUint {
+    assert!(!n.is_zero(), "random_below requires a positive bound");
+
+    let bits = n.bits();
+    loop {
+        let candidate = random_bits(bits);
+        if candidate < *n {
+            return candidate;
+        }
+    }
+}
+
+/// Uniformly random integer in [low, high)
+pub fn random_range(low: &BigUint, high: &BigUint) -> BigUint {
+    assert!(low < high, "random_range requires low < high");
+    low + random_below(&(high - low))
+}
+
+/// Miller-Rabin with `rounds` random bases
+pub fn is_probable_prime(n: &BigUint, rounds: usize) -> bool {
+    let two = BigUint::from(2u32);
+    if *n < two {
+        return false;
+    }
+
+    for &p in SMALL_PRIMES.iter() {
+        if *n == BigUint::from(p) {
+            return true;
+        }
+        if (n % p).is_zero() {
+            return false;
+        }
+    }
+
+    // n - 1 = d * 2^s with d odd
+    let n_minus_one = n - 1u32;
+    let s = n_minus_one.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_one >> s;
+
+    'witness: for _ in 0..rounds {
+        let a = random_range(&two, &n_minus_one);
+        let mut x = a.modpow(&d, n);
+
+        if x.is_one() || x == n_minus_one {
+            continue;
+        }
+
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// Random prime with exactly `bits` bits
+pub fn random_prime(bits: u64) -> BigUint {
+    assert!(bits >= 2, "a prime needs at least two bits");
+
+    loop {
+        // Force the top bit so the size is exact, and the bottom bit so it's odd
+        let mut candidate = random_bits(bits);
+        candidate.set_bit(bits - 1, true);
+        candidate.set_bit(0, true);
+
+        if is_probable_prime(&candidate, PRIME_ROUNDS) {
+            return candidate;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_miller_rabin() {
+        assert!(is_probable_prime(&BigUint::from(2u32), 20));
+        assert!(is_probable_prime(&BigUint::from(7919u32), 20));
+        assert!(is_probable_prime(&((BigUint::one() << 127u32) - 1u32), 20));
+
+        // Carmichael numbers fool Fermat but not Miller-Rabin
+        assert!(!is_probable_prime(&BigUint::from(561u32), 20));
+        assert!(!is_probable_prime(&BigUint::from(41041u32), 20));
+        assert!(!is_probable_prime(&((BigUint::one() << 128u32) - 1u32), 20));
+    }
+
+    #[test]
+    fn test_random_prime_has_exact_size() {
+        for bits in [16, 64, 256] {
+            let p = random_prime(bits);
+            assert_eq!(p.bits(), bits);
+            assert!(is_probable_prime(&p, 20));
+        }
+    }
+}
@@ -0,0 +1,137 @@
+// crypto/src/cbc_mac/attacks.rs
+use crate::aes::{cbc, pkcs7_pad, AES128_BLOCK_SIZE};
+
+use super::transaction::{Transaction, TransactionClient, Transfer};
+
+/// With a client-chosen IV, the first plaintext block is only protected by
+/// IV XOR P1. Rewriting `from=<attacker>` to `from=<victim>` in a request the
+/// attacker signed for themselves and XORing the same difference into the IV
+/// leaves the MAC unchanged. The two account numbers must be the same length.
+pub fn forge_transfer_source(request: &[u8], victim: u32) -> Option<Vec<u8>> {
+    if request.len() < 3 * AES128_BLOCK_SIZE {
+        return None;
+    }
+    let (message, trailer) = request.split_at(request.len() - 2 * AES128_BLOCK_SIZE);
+    let (iv, mac) = trailer.split_at(AES128_BLOCK_SIZE);
+
+    let end = message.iter().position(|&b| b == b'&')?;
+    let victim = format!("from={}", victim).into_bytes();
+    if !message.starts_with(b"from=") || victim.len() != end || end > AES128_BLOCK_SIZE {
+        return None;
+    }
+
+    let mut forged = message.to_vec();
+    forged[..end].copy_from_slice(&victim);
+
+    let forged_iv: Vec<u8> = iv.iter()
+        .zip(&message[..AES128_BLOCK_SIZE])
+        .zip(&forged[..AES128_BLOCK_SIZE])
+        .map(|((iv, old), new)| iv ^ old ^ new)
+        .collect();
+
+    Some([forged, forged_iv, mac.to_vec()].concat())
+}
+
+/// Length extension with a fixed IV: the MAC of a captured message is the chain
+/// state after its padding, so gluing `pad(M1) || (M2[0] ^ t1) || M2[1..]` carries
+/// the attacker's own MAC t2 over. The glue block turns into garbage that the
+/// lenient parser skips, unless it happens to contain a `&` and cut the list short;
+/// the glue only depends on t1, so that capture is unusable and None is returned.
+pub fn extend_transaction(captured: &[u8], client: &TransactionClient, amount: u64) -> Option<Vec<u8>> {
+    if captured.len() < AES128_BLOCK_SIZE {
+        return None;
+    }
+    let (message, mac) = captured.split_at(captured.len() - AES128_BLOCK_SIZE);
+
+    // The first transfer shares a block with the glue and is sacrificed to it
+    let wanted = Transfer { to: client.account(), amount };
+    let own = client.transfer_list(&[Transfer { to: client.account(), amount: 1 }, wanted.clone()]);
+    let (own_message, own_mac) = own.split_at(own.len() - AES128_BLOCK_SIZE);
+
+    let glue: Vec<u8> = own_message[..AES128_BLOCK_SIZE].iter().zip(mac).map(|(a, b)| a ^ b).collect();
+    let forged = [pkcs7_pad(message, AES128_BLOCK_SIZE), glue, own_message[AES128_BLOCK_SIZE..].to_vec()].concat();
+
+    match Transaction::parse(&forged) {
+        Ok(transaction) if transaction.transfers.contains(&wanted) => Some([forged, own_mac.to_vec()].concat()),
+        _ => None,
+    }
+}
+
+/// CBC-MAC used as a hash with a public key is trivially second-preimageable:
+/// run the payload through the cipher, then choose the next block so the chain
+/// lands back on the original's state after its first block. The payload ends in
+/// `//` so everything after it is a comment, and comment filler is varied until the
+/// glue block contains no line terminators. Returns None if the original is
+/// shorter than a block.
+pub fn forge_javascript(original: &[u8], payload: &[u8], key: &[u8]) -> Option<Vec<u8>> {
+    if original.len() < AES128_BLOCK_SIZE {
+        return None;
+    }
+
+    for attempt in 0u32..4096 {
+        let mut prefix = [payload, b"//", attempt.to_string().as_bytes()].concat();
+        prefix.resize(prefix.len().next_multiple_of(AES128_BLOCK_SIZE), b' ');
+
+        let chain = cbc::encrypt(&prefix, key, &[0u8; AES128_BLOCK_SIZE]).ok()?;
+        let state = &chain[chain.len() - AES128_BLOCK_SIZE..];
+        let glue: Vec<u8> = state.iter().zip(&original[..AES128_BLOCK_SIZE]).map(|(a, b)| a ^ b).collect();
+
+        if !glue.iter().any(|&b| b == b'\n' || b == b'\r') {
+            return Some([prefix, glue, original[AES128_BLOCK_SIZE..].to_vec()].concat());
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbc_mac::{cbc_mac, TransactionServer};
+    use crate::utils::oracle::random_aes_key;
+
+    #[test]
+    fn test_forge_transfer_source() {
+        let key = random_aes_key();
+        let attacker = TransactionClient::new(key, 1337);
+        let server = TransactionServer::new(key);
+
+        let request = attacker.transfer_with_iv(1337, 1_000_000);
+        let forged = forge_transfer_source(&request, 4242).unwrap();
+
+        let transaction = server.process_with_iv(&forged).unwrap();
+        assert_eq!(transaction.from, 4242);
+        assert_eq!(transaction.transfers, vec![Transfer { to: 1337, amount: 1_000_000 }]);
+    }
+
+    #[test]
+    fn test_extend_transaction() {
+        let key = random_aes_key();
+        let victim = TransactionClient::new(key, 4242);
+        let attacker = TransactionClient::new(key, 1337);
+        let server = TransactionServer::new(key);
+
+        // Roughly one capture in sixteen has a '&' in its glue block, so keep listening
+        let forged = (1..=100)
+            .map(|amount| victim.transfer_list(&[Transfer { to: 10, amount }, Transfer { to: 11, amount: 50 }]))
+            .find_map(|captured| extend_transaction(&captured, &attacker, 1_000_000))
+            .unwrap();
+
+        let transaction = server.process_fixed_iv(&forged).unwrap();
+        assert_eq!(transaction.from, 4242);
+        assert!(transaction.transfers.contains(&Transfer { to: 1337, amount: 1_000_000 }));
+    }
+
+    #[test]
+    fn test_forge_javascript() {
+        let original = b"alert('MZA who was that?');\n";
+        let payload = b"alert('Ayo, the Wu is back!');";
+        let key = b"YELLOW SUBMARINE";
+        let iv = [0u8; 16];
+
+        let forged = forge_javascript(original, payload, key).unwrap();
+        assert_eq!(cbc_mac(&forged, key, &iv).unwrap(), cbc_mac(original, key, &iv).unwrap());
+        assert!(forged.starts_with(b"alert('Ayo, the Wu is back!');//"));
+        assert!(!forged[..forged.len() - 1].iter().any(|&b| b == b'\n' || b == b'\r'));
+    }
+}
@@ -0,0 +1,44 @@
+// crypto/src/cbc_mac/mod.rs
+pub mod attacks;
+pub mod transaction;
+
+pub use attacks::{extend_transaction, forge_javascript, forge_transfer_source};
+pub use transaction::{Transaction, TransactionClient, TransactionServer, Transfer};
+
+use crate::aes::{cbc, pkcs7_pad, AES128_BLOCK_SIZE};
+use crate::common::AesError;
+
+/// CBC-MAC: PKCS#7 pad, CBC-encrypt, keep the last ciphertext block
+pub fn cbc_mac(message: &[u8], key: &[u8], iv: &[u8]) -> Result<[u8; AES128_BLOCK_SIZE], AesError> {
+    let ciphertext = cbc::encrypt(&pkcs7_pad(message, AES128_BLOCK_SIZE), key, iv)?;
+
+    let mut mac = [0u8; AES128_BLOCK_SIZE];
+    mac.copy_from_slice(&ciphertext[ciphertext.len() - AES128_BLOCK_SIZE..]);
+    Ok(mac)
+}
+
+pub fn verify_cbc_mac(message: &[u8], key: &[u8], iv: &[u8], mac: &[u8]) -> bool {
+    match cbc_mac(message, key, iv) {
+        Ok(expected) => expected
+            .iter()
+            .zip(mac)
+            .fold(mac.len() ^ expected.len(), |diff, (a, b)| diff | (a ^ b) as usize) == 0,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serialize::to_hex;
+
+    #[test]
+    fn test_cbc_mac() {
+        let mac = cbc_mac(b"alert('MZA who was that?');\n", b"YELLOW SUBMARINE", &[0u8; 16]).unwrap();
+        assert_eq!(to_hex(&mac), "296b8d7cb78a243dda4d0a61d33bbdd1");
+
+        assert!(verify_cbc_mac(b"alert('MZA who was that?');\n", b"YELLOW SUBMARINE", &[0u8; 16], &mac));
+        assert!(!verify_cbc_mac(b"alert('MZA who was this?');\n", b"YELLOW SUBMARINE", &[0u8; 16], &mac));
+        assert!(!verify_cbc_mac(b"alert('MZA who was that?');\n", b"YELLOW SUBMARINE", &[0u8; 16], &mac[..15]));
+    }
+}
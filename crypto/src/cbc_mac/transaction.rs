@@ -0,0 +1,179 @@
+// crypto/src/cbc_mac/transaction.rs
+use crate::aes::AES128_BLOCK_SIZE;
+use crate::common::MacError;
+use crate::utils::oracle::random_iv;
+
+use super::{cbc_mac, verify_cbc_mac};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transfer {
+    pub to: u32,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transaction {
+    pub from: u32,
+    pub transfers: Vec<Transfer>,
+}
+
+impl Transaction {
+    /// Accepts both `from=#&to=#&amount=#` and `from=#&tx_list=#:#;#:#`. Like the
+    /// quick-and-dirty server it stands in for, it splits on delimiters, keeps the
+    /// first occurrence of each key and skips tx_list entries it can't read.
+    pub fn parse(message: &[u8]) -> Result<Self, MacError> {
+        let field = |name: &[u8]| {
+            message.split(|&b| b == b'&')
+                .filter_map(|pair| {
+                    let split = pair.iter().position(|&b| b == b'=')?;
+                    Some((&pair[..split], &pair[split + 1..]))
+                })
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value)
+        };
+
+        let from = field(b"from")
+            .and_then(parse_number)
+            .ok_or_else(|| MacError::Malformed("missing or invalid 'from'".to_string()))?;
+
+        let transfers = if let Some(list) = field(b"tx_list") {
+            list.split(|&b| b == b';').filter_map(parse_transfer).collect()
+        } else {
+            let to = field(b"to").and_then(parse_number);
+            let amount = field(b"amount").and_then(parse_number);
+            match (to, amount) {
+                (Some(to), Some(amount)) => vec![Transfer { to, amount }],
+                _ => return Err(MacError::Malformed("expected 'to' and 'amount' or 'tx_list'".to_string())),
+            }
+        };
+
+        Ok(Transaction { from, transfers })
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(digits: &[u8]) -> Option<T> {
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    std::str::from_utf8(digits).ok()?.parse().ok()
+}
+
+fn parse_transfer(entry: &[u8]) -> Option<Transfer> {
+    let split = entry.iter().position(|&b| b == b':')?;
+    Some(Transfer {
+        to: parse_number(&entry[..split])?,
+        amount: parse_number(&entry[split + 1..])?,
+    })
+}
+
+fn split_trailer(request: &[u8], len: usize) -> Result<(&[u8], &[u8]), MacError> {
+    if request.len() < len {
+        return Err(MacError::Malformed("request too short".to_string()));
+    }
+    Ok(request.split_at(request.len() - len))
+}
+
+/// Shares the MAC key with the server, which is why it's the only thing that
+/// enforces who the money comes from
+pub struct TransactionClient {
+    key: [u8; 16],
+    account: u32,
+}
+
+impl TransactionClient {
+    pub fn new(key: [u8; 16], account: u32) -> Self {
+        Self { key, account }
+    }
+
+    pub fn account(&self) -> u32 {
+        self.account
+    }
+
+    /// `message || IV || MAC` with a fresh random IV per request
+    pub fn transfer_with_iv(&self, to: u32, amount: u64) -> Vec<u8> {
+        let message = format!("from={}&to={}&amount={}", self.account, to, amount).into_bytes();
+        let iv = random_iv();
+        let mac = cbc_mac(&message, &self.key, &iv).expect("16-byte key");
+
+        [message, iv.to_vec(), mac.to_vec()].concat()
+    }
+
+    /// `message || MAC` under the fixed all-zero IV
+    pub fn transfer_list(&self, transfers: &[Transfer]) -> Vec<u8> {
+        let list = transfers.iter()
+            .map(|t| format!("{}:{}", t.to, t.amount))
+            .collect::<Vec<_>>()
+            .join(";");
+        let message = format!("from={}&tx_list={}", self.account, list).into_bytes();
+        let mac = cbc_mac(&message, &self.key, &[0u8; AES128_BLOCK_SIZE]).expect("16-byte key");
+
+        [message, mac.to_vec()].concat()
+    }
+}
+
+pub struct TransactionServer {
+    key: [u8; 16],
+}
+
+impl TransactionServer {
+    pub fn new(key: [u8; 16]) -> Self {
+        Self { key }
+    }
+
+    /// Verifies a `message || IV || MAC` request from the first version of the API
+    pub fn process_with_iv(&self, request: &[u8]) -> Result<Transaction, MacError> {
+        let (rest, mac) = split_trailer(request, AES128_BLOCK_SIZE)?;
+        let (message, iv) = split_trailer(rest, AES128_BLOCK_SIZE)?;
+
+        if !verify_cbc_mac(message, &self.key, iv, mac) {
+            return Err(MacError::InvalidMac);
+        }
+        Transaction::parse(message)
+    }
+
+    /// Verifies a `message || MAC` request from the fixed-IV version of the API
+    pub fn process_fixed_iv(&self, request: &[u8]) -> Result<Transaction, MacError> {
+        let (message, mac) = split_trailer(request, AES128_BLOCK_SIZE)?;
+
+        if !verify_cbc_mac(message, &self.key, &[0u8; AES128_BLOCK_SIZE], mac) {
+            return Err(MacError::InvalidMac);
+        }
+        Transaction::parse(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::oracle::random_aes_key;
+
+    #[test]
+    fn test_parse() {
+        let single = Transaction::parse(b"from=1&to=2&amount=300").unwrap();
+        assert_eq!(single, Transaction { from: 1, transfers: vec![Transfer { to: 2, amount: 300 }] });
+
+        let list = Transaction::parse(b"from=1&tx_list=2:300;x:y;4:5").unwrap();
+        assert_eq!(list.transfers, vec![Transfer { to: 2, amount: 300 }, Transfer { to: 4, amount: 5 }]);
+
+        assert!(Transaction::parse(b"from=1&to=2").is_err());
+        assert!(Transaction::parse(b"to=2&amount=3").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let key = random_aes_key();
+        let client = TransactionClient::new(key, 7);
+        let server = TransactionServer::new(key);
+
+        let request = client.transfer_with_iv(8, 100);
+        assert_eq!(server.process_with_iv(&request).unwrap().transfers, vec![Transfer { to: 8, amount: 100 }]);
+
+        let mut tampered = request.clone();
+        tampered[5] ^= 1;
+        assert!(matches!(server.process_with_iv(&tampered), Err(MacError::InvalidMac)));
+
+        let transfers = vec![Transfer { to: 8, amount: 100 }, Transfer { to: 9, amount: 5 }];
+        let request = client.transfer_list(&transfers);
+        assert_eq!(server.process_fixed_iv(&request).unwrap(), Transaction { from: 7, transfers });
+    }
+}
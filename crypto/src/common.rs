@@ -3,4 +3,59 @@ pub enum AesError {
     InvalidKeyLength,
     InvalidBlockSize,
     InvalidPadding,
-}
\ No newline at end of file
+    AuthenticationFailed,
+}
+
+#[derive(Debug, Clone)]
+pub enum DhError {
+    UnexpectedMessage,
+    EchoMismatch,
+    Aes(AesError),
+}
+
+impl From<AesError> for DhError {
+    fn from(e: AesError) -> Self {
+        DhError::Aes(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SrpError {
+    UnknownUser,
+    UnexpectedMessage,
+    InvalidPublicKey,
+    NoSession,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RsaError {
+    MessageTooLarge,
+    InvalidKey,
+    InvalidPadding,
+    RefusedCiphertext,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DsaError {
+    InvalidParameters,
+    InvalidNonce,
+    InvalidFormat(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum MacError {
+    InvalidMac,
+    Malformed(String),
+    Aes(AesError),
+}
+
+impl From<AesError> for MacError {
+    fn from(e: AesError) -> Self {
+        MacError::Aes(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EcError {
+    InvalidPoint,
+}
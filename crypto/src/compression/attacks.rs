@@ -0,0 +1,98 @@
+// crypto/src/compression/attacks.rs
+const KNOWN_PREFIX: &[u8] = b"sessionid=";
+const SEPARATOR: &[u8] = b"~#";
+const JUNK_LENGTHS: usize = 48;
+const JUNK_FLAVOURS: usize = 4;
+/// How many boundary crossings the winner must lead by before we stop measuring
+const CONFIDENCE: isize = 2;
+/// Base64 plus the newline that ends the cookie header
+const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=\n";
+
+/// Recovers the cookie one byte at a time. A correct guess extends the
+/// back-reference to the real cookie instead of costing a literal, which saves a
+/// few bits. Each candidate is measured both inline (`known || c || sep`) and
+/// apart (`known || sep || c`) so Huffman-table effects of the extra literal cancel,
+/// and only the saving remains.
+///
+/// Those bits only show when they move the output across a byte (stream) or a
+/// block (CBC) boundary, so the pair is measured behind junk prefixes of varying
+/// length and content to sweep the alignment, accumulating until one candidate
+/// leads clearly. The junk is distinct high bytes so it never compresses.
+pub fn recover_session_id<F>(mut length: F, max_len: usize) -> Option<String>
+where
+    F: FnMut(&[u8]) -> usize,
+{
+    let mut known = Vec::new();
+
+    while known.len() < max_len {
+        match next_byte(&mut length, &known)? {
+            b'\n' => break,
+            byte => known.push(byte),
+        }
+    }
+
+    String::from_utf8(known).ok()
+}
+
+fn next_byte<F>(length: &mut F, known: &[u8]) -> Option<u8>
+where
+    F: FnMut(&[u8]) -> usize,
+{
+    let mut scores = vec![0isize; ALPHABET.len()];
+    // The smallest length difference seen: 1 for a stream cipher, a block for CBC
+    let mut unit = isize::MAX;
+
+    'measure: for flavour in 0..JUNK_FLAVOURS {
+        for junk_len in 0..JUNK_LENGTHS {
+            let junk: Vec<u8> = (0..junk_len).map(|i| 0x80 | ((7 * i + 13 * flavour) % 0x80) as u8).collect();
+
+            for (score, &candidate) in scores.iter_mut().zip(ALPHABET) {
+                let inline = [&junk, KNOWN_PREFIX, known, &[candidate], SEPARATOR].concat();
+                let apart = [&junk, KNOWN_PREFIX, known, SEPARATOR, &[candidate]].concat();
+
+                let difference = length(&inline) as isize - length(&apart) as isize;
+                if difference != 0 {
+                    unit = unit.min(difference.abs());
+                }
+                *score += difference;
+            }
+
+            let mut ranked = scores.clone();
+            ranked.sort_unstable();
+            if unit != isize::MAX && ranked[1] - ranked[0] >= CONFIDENCE * unit {
+                break 'measure;
+            }
+        }
+    }
+
+    let best = *scores.iter().min()?;
+    let mut winners = scores.iter().zip(ALPHABET).filter(|&(&score, _)| score == best);
+
+    match (winners.next(), winners.next()) {
+        (Some((_, &byte)), None) => Some(byte),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::{CompressionOracle, OracleCipher, DEFAULT_SESSION_ID};
+    use serialize::to_base64;
+    use crate::utils::oracle::random_bytes;
+
+    #[test]
+    fn test_recover_stream() {
+        let mut oracle = CompressionOracle::new(DEFAULT_SESSION_ID, OracleCipher::Stream);
+        let recovered = recover_session_id(|body| oracle.length(body), 64).unwrap();
+        assert_eq!(recovered, DEFAULT_SESSION_ID);
+    }
+
+    #[test]
+    fn test_recover_cbc() {
+        let session_id = to_base64(&random_bytes(24));
+        let mut oracle = CompressionOracle::new(&session_id, OracleCipher::Cbc);
+        let recovered = recover_session_id(|body| oracle.length(body), 64).unwrap();
+        assert_eq!(recovered, session_id);
+    }
+}
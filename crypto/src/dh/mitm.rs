@@ -0,0 +1,202 @@
+// crypto/src/dh/mitm.rs
+use crate::bigint::{BigUint, One, Zero};
+
+use super::protocol::Message;
+use super::Kdf;
+
+/// Sits between A and B and may rewrite anything in flight
+pub trait Mitm {
+    fn a_to_b(&mut self, message: Message) -> Message {
+        message
+    }
+
+    fn b_to_a(&mut self, message: Message) -> Message {
+        message
+    }
+}
+
+/// An honest wire
+pub struct Passive;
+
+impl Mitm for Passive {}
+
+/// Swap both public keys for p, so each side computes s = p^x mod p = 0
+pub struct ParameterInjection {
+    kdf: Kdf,
+    p: Option<BigUint>,
+    pub recovered: Vec<Vec<u8>>,
+}
+
+impl ParameterInjection {
+    pub fn new(kdf: Kdf) -> Self {
+        Self { kdf, p: None, recovered: Vec::new() }
+    }
+
+    fn intercept(&mut self, message: Message) -> Message {
+        match message {
+            Message::Init { p, g, .. } => {
+                self.p = Some(p.clone());
+                Message::Init { public: p.clone(), p, g }
+            }
+            Message::PublicKey(_) => Message::PublicKey(self.p.clone().unwrap_or_default()),
+            Message::Data { .. } => {
+                let key = self.kdf.derive(&BigUint::zero());
+                if let Ok(plaintext) = message.open(&key) {
+                    self.recovered.push(plaintext);
+                }
+                message
+            }
+            other => other,
+        }
+    }
+}
+
+impl Mitm for ParameterInjection {
+    fn a_to_b(&mut self, message: Message) -> Message {
+        self.intercept(message)
+    }
+
+    fn b_to_a(&mut self, message: Message) -> Message {
+        self.intercept(message)
+    }
+}
+
+/// Generators that pin the shared secret to a value the attacker can predict
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaliciousG {
+    /// g = 1: every public key and the secret are 1
+    One,
+    /// g = p: every public key and the secret are 0
+    P,
+    /// g = p - 1: the secret is p - 1 when both exponents are odd, otherwise 1
+    PMinusOne,
+}
+
+/// Rewrite g during negotiation so both sides agree on a degenerate group
+pub struct MaliciousGroupAttack {
+    kdf: Kdf,
+    choice: MaliciousG,
+    p: BigUint,
+    public_keys: Vec<BigUint>,
+    pub recovered: Vec<Vec<u8>>,
+}
+
+impl MaliciousGroupAttack {
+    pub fn new(kdf: Kdf, choice: MaliciousG) -> Self {
+        Self {
+            kdf,
+            choice,
+            p: BigUint::zero(),
+            public_keys: Vec::new(),
+            recovered: Vec::new(),
+        }
+    }
+
+    fn malicious_g(&self) -> BigUint {
+        match self.choice {
+            MaliciousG::One => BigUint::one(),
+            MaliciousG::P => self.p.clone(),
+            MaliciousG::PMinusOne => &self.p - 1u32,
+        }
+    }
+
+    fn predicted_secret(&self) -> BigUint {
+        match self.choice {
+            MaliciousG::One => BigUint::one(),
+            MaliciousG::P => BigUint::zero(),
+            MaliciousG::PMinusOne => {
+                // (p-1)^(ab) is p-1 only if a and b are both odd, which shows in A and B
+                let p_minus_one = &self.p - 1u32;
+                if self.public_keys.len() == 2 && self.public_keys.iter().all(|k| *k == p_minus_one) {
+                    p_minus_one
+                } else {
+                    BigUint::one()
+                }
+            }
+        }
+    }
+
+    fn intercept(&mut self, message: Message) -> Message {
+        match message {
+            Message::Negotiate { p, .. } => {
+                self.p = p.clone();
+                Message::Negotiate { g: self.malicious_g(), p }
+            }
+            Message::Ack { p, .. } => Message::Ack { g: self.malicious_g(), p },
+            Message::PublicKey(public) => {
+                self.public_keys.push(public.clone());
+                Message::PublicKey(public)
+            }
+            Message::Data { .. } => {
+                let key = self.kdf.derive(&self.predicted_secret());
+                if let Ok(plaintext) = message.open(&key) {
+                    self.recovered.push(plaintext);
+                }
+                message
+            }
+            other => other,
+        }
+    }
+}
+
+impl Mitm for MaliciousGroupAttack {
+    fn a_to_b(&mut self, message: Message) -> Message {
+        self.intercept(message)
+    }
+
+    fn b_to_a(&mut self, message: Message) -> Message {
+        self.intercept(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dh::protocol::{run_negotiated_protocol, run_protocol};
+    use crate::dh::DhGroup;
+
+    const MESSAGES: [&[u8]; 3] = [
+        b"Hello Bob",
+        b"The quick brown fox jumps over the lazy dog",
+        b"16 bytes exactly",
+    ];
+
+    fn expected() -> Vec<Vec<u8>> {
+        MESSAGES.iter().map(|m| m.to_vec()).collect()
+    }
+
+    /// Each message crosses the wire twice: A's original and B's echo
+    fn both_ways() -> Vec<Vec<u8>> {
+        expected().into_iter().flat_map(|m| [m.clone(), m]).collect()
+    }
+
+    #[test]
+    fn test_honest_exchange() {
+        let transcript = run_protocol(&DhGroup::nist(), Kdf::Sha1, &MESSAGES, &mut Passive).unwrap();
+        assert_eq!(transcript.received_by_b, expected());
+        assert_eq!(transcript.received_by_a, expected());
+    }
+
+    #[test]
+    fn test_parameter_injection_reads_every_message() {
+        let mut mitm = ParameterInjection::new(Kdf::Sha1);
+        let transcript = run_protocol(&DhGroup::nist(), Kdf::Sha1, &MESSAGES, &mut mitm).unwrap();
+
+        // A and B notice nothing, while M sees both directions
+        assert_eq!(transcript.received_by_b, expected());
+        assert_eq!(mitm.recovered, both_ways());
+    }
+
+    #[test]
+    fn test_malicious_g_reads_every_message() {
+        for choice in [MaliciousG::One, MaliciousG::P, MaliciousG::PMinusOne] {
+            for kdf in [Kdf::Sha1, Kdf::Sha256] {
+                let mut mitm = MaliciousGroupAttack::new(kdf, choice);
+                let transcript = run_negotiated_protocol(&DhGroup::nist(), kdf, &MESSAGES, &mut mitm).unwrap();
+
+                assert_eq!(transcript.received_by_a, expected());
+                assert_eq!(mitm.recovered, both_ways(), "{:?}", choice);
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// crypto/src/dh/mod.rs
+pub mod mitm;
+pub mod protocol;
+pub mod subgroup;
+
+pub use mitm::{MaliciousG, MaliciousGroupAttack, Mitm, ParameterInjection, Passive};
+pub use protocol::{run_negotiated_protocol, run_protocol, Message, Transcript};
+pub use subgroup::{recover_private_key, subgroup_confinement, DhSubgroup, MacOracle};
+
+use crate::bigint::{from_hex, modexp, random_range, BigUint, One};
+use crate::hash::{sha1, sha256};
+use crate::hmac::hmac_sha256;
+use crate::kangaroo::KangarooGroup;
+
+/// The 1536-bit MODP group from RFC 3526, section 2
+const NIST_P: &str = "
+    ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024
+    e088a67cc74020bbea63b139b22514a08798e3404ddef9519b3cd
+    3a431b302b0a6df25f14374fe1356d6d51c245e485b576625e7ec
+    6f44c42e9a637ed6b0bff5cb6f406b7edee386bfb5a899fa5ae9f
+    24117c4b1fe649286651ece45b3dc2007cb8a163bf0598da48361
+    c55d39a69163fa8fd24cf5f83655d23dca3ad961c62f356208552
+    bb9ed529077096966d670c354e4abc9804f1746c08ca237327fff
+    fffffffffffff";
+const NIST_G: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DhGroup {
+    pub p: BigUint,
+    pub g: BigUint,
+}
+
+impl DhGroup {
+    pub fn new(p: BigUint, g: BigUint) -> Self {
+        Self { p, g }
+    }
+
+    pub fn nist() -> Self {
+        Self {
+            p: from_hex(NIST_P).unwrap(),
+            g: BigUint::from(NIST_G),
+        }
+    }
+}
+
+impl KangarooGroup for DhGroup {
+    type Element = BigUint;
+
+    fn power(&self, k: &BigUint) -> BigUint {
+        modexp(&self.g, k, &self.p)
+    }
+
+    fn combine(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b % &self.p
+    }
+
+    fn index(&self, element: &BigUint, k: u32) -> u32 {
+        (element % k).try_into().unwrap()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DhKeyPair {
+    private: BigUint,
+    pub public: BigUint,
+}
+
+impl DhKeyPair {
+    pub fn generate(group: &DhGroup) -> Self {
+        let private = random_range(&BigUint::one(), &(&group.p - 1u32));
+        Self::from_private(group, private)
+    }
+
+    pub fn from_private(group: &DhGroup, private: BigUint) -> Self {
+        let public = modexp(&group.g, &private, &group.p);
+        Self { private, public }
+    }
+
+    pub fn private_key(&self) -> &BigUint {
+        &self.private
+    }
+
+    pub fn shared_secret(&self, group: &DhGroup, other_public: &BigUint) -> BigUint {
+        modexp(other_public, &self.private, &group.p)
+    }
+}
+
+/// How a shared secret is turned into an AES-128 key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kdf {
+    Sha1,
+    Sha256,
+}
+
+impl Kdf {
+    /// First 16 bytes of the hash of the big-endian secret
+    pub fn derive(&self, secret: &BigUint) -> [u8; 16] {
+        let bytes = secret.to_bytes_be();
+        let digest = match self {
+            Kdf::Sha1 => sha1(&bytes),
+            Kdf::Sha256 => sha256(&bytes),
+        };
+
+        digest[..16].try_into().unwrap()
+    }
+}
+
+/// What the small-subgroup and invalid-curve oracles authenticate under the
+/// shared secret
+pub const ORACLE_MESSAGE: &[u8] = b"crazy flamboyant for the rap enjoyment";
+
+/// The oracles' reply: HMAC-SHA256 of the oracle message keyed by the
+/// SHA-256 KDF of the shared secret
+pub fn secret_mac(secret: &BigUint) -> Vec<u8> {
+    hmac_sha256(&Kdf::Sha256.derive(secret), ORACLE_MESSAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_group_agreement() {
+        let group = DhGroup::new(BigUint::from(37u32), BigUint::from(5u32));
+        let alice = DhKeyPair::generate(&group);
+        let bob = DhKeyPair::generate(&group);
+
+        assert_eq!(
+            alice.shared_secret(&group, &bob.public),
+            bob.shared_secret(&group, &alice.public)
+        );
+    }
+
+    #[test]
+    fn test_nist_group_agreement() {
+        let group = DhGroup::nist();
+        assert_eq!(group.p.bits(), 1536);
+
+        let alice = DhKeyPair::generate(&group);
+        let bob = DhKeyPair::generate(&group);
+        let s = alice.shared_secret(&group, &bob.public);
+
+        assert_eq!(s, bob.shared_secret(&group, &alice.public));
+        assert_eq!(Kdf::Sha256.derive(&s), Kdf::Sha256.derive(&bob.shared_secret(&group, &alice.public)));
+    }
+}
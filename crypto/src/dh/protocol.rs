@@ -0,0 +1,134 @@
+// crypto/src/dh/protocol.rs
+use crate::aes::{aes128_decrypt, aes128_encrypt, pkcs7_pad, pkcs7_unpad, AesMode, AES128_BLOCK_SIZE};
+use crate::bigint::BigUint;
+use crate::common::{AesError, DhError};
+use crate::utils::oracle::random_iv;
+
+use super::mitm::Mitm;
+use super::{DhGroup, DhKeyPair, Kdf};
+
+/// Everything that crosses the wire between A and B
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// Fixed-group handshake: A sends the group along with its public key
+    Init { p: BigUint, g: BigUint, public: BigUint },
+    /// Negotiated-group handshake: A proposes a group, B acknowledges it
+    Negotiate { p: BigUint, g: BigUint },
+    Ack { p: BigUint, g: BigUint },
+    PublicKey(BigUint),
+    /// AES-128-CBC(key, msg) with the IV appended, as in the cryptopals protocol
+    Data { ciphertext: Vec<u8>, iv: [u8; AES128_BLOCK_SIZE] },
+}
+
+impl Message {
+    pub fn seal(key: &[u8], plaintext: &[u8]) -> Result<Self, AesError> {
+        let iv = random_iv();
+        let ciphertext = aes128_encrypt(&pkcs7_pad(plaintext, AES128_BLOCK_SIZE), key, AesMode::CBC { iv })?;
+        Ok(Message::Data { ciphertext, iv })
+    }
+
+    pub fn open(&self, key: &[u8]) -> Result<Vec<u8>, DhError> {
+        match self {
+            Message::Data { ciphertext, iv } => {
+                let padded = aes128_decrypt(ciphertext, key, AesMode::CBC { iv: *iv })?;
+                Ok(pkcs7_unpad(&padded)?)
+            }
+            _ => Err(DhError::UnexpectedMessage),
+        }
+    }
+}
+
+/// What each end saw once the handshake was done
+#[derive(Debug, Clone, Default)]
+pub struct Transcript {
+    /// Plaintexts B decrypted from A
+    pub received_by_b: Vec<Vec<u8>>,
+    /// Echoes A decrypted from B
+    pub received_by_a: Vec<Vec<u8>>,
+}
+
+/// A -> B: p, g, A; B -> A: B; then each message is sent and echoed back.
+pub fn run_protocol<M: Mitm + ?Sized>(
+    group: &DhGroup,
+    kdf: Kdf,
+    messages: &[&[u8]],
+    mitm: &mut M,
+) -> Result<Transcript, DhError> {
+    let alice = DhKeyPair::generate(group);
+    let init = Message::Init { p: group.p.clone(), g: group.g.clone(), public: alice.public.clone() };
+
+    let Message::Init { p, g, public: a_public } = mitm.a_to_b(init) else {
+        return Err(DhError::UnexpectedMessage);
+    };
+    let bob_group = DhGroup::new(p, g);
+    let bob = DhKeyPair::generate(&bob_group);
+    let bob_key = kdf.derive(&bob.shared_secret(&bob_group, &a_public));
+
+    let Message::PublicKey(b_public) = mitm.b_to_a(Message::PublicKey(bob.public.clone())) else {
+        return Err(DhError::UnexpectedMessage);
+    };
+    let alice_key = kdf.derive(&alice.shared_secret(group, &b_public));
+
+    exchange(&alice_key, &bob_key, messages, mitm)
+}
+
+/// A -> B: p, g; B -> A: ACK; A -> B: A; B -> A: B; then the echo exchange.
+/// Both ends use the group as acknowledged, so whoever controls the wire picks it.
+pub fn run_negotiated_protocol<M: Mitm + ?Sized>(
+    group: &DhGroup,
+    kdf: Kdf,
+    messages: &[&[u8]],
+    mitm: &mut M,
+) -> Result<Transcript, DhError> {
+    let Message::Negotiate { p, g } = mitm.a_to_b(Message::Negotiate { p: group.p.clone(), g: group.g.clone() }) else {
+        return Err(DhError::UnexpectedMessage);
+    };
+    let bob_group = DhGroup::new(p, g);
+
+    let ack = Message::Ack { p: bob_group.p.clone(), g: bob_group.g.clone() };
+    let Message::Ack { p, g } = mitm.b_to_a(ack) else {
+        return Err(DhError::UnexpectedMessage);
+    };
+    let alice_group = DhGroup::new(p, g);
+
+    let alice = DhKeyPair::generate(&alice_group);
+    let Message::PublicKey(a_public) = mitm.a_to_b(Message::PublicKey(alice.public.clone())) else {
+        return Err(DhError::UnexpectedMessage);
+    };
+
+    let bob = DhKeyPair::generate(&bob_group);
+    let Message::PublicKey(b_public) = mitm.b_to_a(Message::PublicKey(bob.public.clone())) else {
+        return Err(DhError::UnexpectedMessage);
+    };
+
+    let alice_key = kdf.derive(&alice.shared_secret(&alice_group, &b_public));
+    let bob_key = kdf.derive(&bob.shared_secret(&bob_group, &a_public));
+
+    exchange(&alice_key, &bob_key, messages, mitm)
+}
+
+fn exchange<M: Mitm + ?Sized>(
+    alice_key: &[u8],
+    bob_key: &[u8],
+    messages: &[&[u8]],
+    mitm: &mut M,
+) -> Result<Transcript, DhError> {
+    let mut transcript = Transcript::default();
+
+    for message in messages {
+        let to_bob = mitm.a_to_b(Message::seal(alice_key, message)?);
+        let received = to_bob.open(bob_key)?;
+
+        let to_alice = mitm.b_to_a(Message::seal(bob_key, &received)?);
+        let echoed = to_alice.open(alice_key)?;
+
+        if echoed != *message {
+            return Err(DhError::EchoMismatch);
+        }
+
+        transcript.received_by_b.push(received);
+        transcript.received_by_a.push(echoed);
+    }
+
+    Ok(transcript)
+}
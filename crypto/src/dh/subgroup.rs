@@ -0,0 +1,187 @@
+// crypto/src/dh/subgroup.rs
+use crate::bigint::{crt, modexp, modinv, random_range, small_factors, BigUint, One, Zero};
+use crate::kangaroo::kangaroo;
+
+use super::{secret_mac, DhGroup, DhKeyPair, ORACLE_MESSAGE};
+
+/// A generator of prime order q inside Z_p^*, where p - 1 = jq and j is left
+/// full of small factors instead of being 2
+#[derive(Debug, Clone, PartialEq)]
+pub struct DhSubgroup {
+    pub group: DhGroup,
+    pub q: BigUint,
+}
+
+impl DhSubgroup {
+    pub fn new(group: DhGroup, q: BigUint) -> Self {
+        Self { group, q }
+    }
+
+    /// The 512-bit group from cryptopals 57: j's factors below 2^16 already
+    /// multiply past q
+    pub fn cryptopals_57() -> Self {
+        Self::from_decimal(
+            "7199773997391911030609999317773941274322764333428698921736339643928346453700085358802973900485592910475480089726140708102474957429903531369589969318716771",
+            "4565356397095740655436854503483826832136106141639563487732438195343690437606117828318042418238184896212352329118608100083187535033402010599512641674644143",
+            "236234353446506858198510045061214171961",
+        )
+    }
+
+    /// The group from cryptopals 58: j's small factors leave about 40 bits of
+    /// the key for the kangaroo
+    pub fn cryptopals_58() -> Self {
+        Self::from_decimal(
+            "11470374874925275658116663507232161402086650258453896274534991676898999262641581519101074740642369848233294239851519212341844337347119899874391456329785623",
+            "622952335333961296978159266084741085889881358738459939978290179936063635566740258555167783009058567397963466103140082647486611657350811560630587013183357",
+            "335062023296420808191071248367701059461",
+        )
+    }
+
+    fn from_decimal(p: &str, g: &str, q: &str) -> Self {
+        let group = DhGroup::new(p.parse().unwrap(), g.parse().unwrap());
+        Self::new(group, q.parse().unwrap())
+    }
+
+    /// j = (p - 1) / q
+    pub fn cofactor(&self) -> BigUint {
+        (&self.group.p - 1u32) / &self.q
+    }
+
+    /// A private key in [1, q)
+    pub fn generate(&self) -> DhKeyPair {
+        DhKeyPair::from_private(&self.group, random_range(&BigUint::one(), &self.q))
+    }
+}
+
+/// Bob, who takes any h as Alice's public key and answers with a MAC keyed
+/// by h^x mod p without checking that h lies in the order-q subgroup
+pub struct MacOracle {
+    params: DhSubgroup,
+    keys: DhKeyPair,
+    pub queries: usize,
+}
+
+impl MacOracle {
+    pub fn new(params: DhSubgroup) -> Self {
+        let keys = params.generate();
+        Self { params, keys, queries: 0 }
+    }
+
+    pub fn public_key(&self) -> &BigUint {
+        &self.keys.public
+    }
+
+    /// The message and its MAC
+    pub fn respond(&mut self, h: &BigUint) -> (Vec<u8>, Vec<u8>) {
+        self.queries += 1;
+        let secret = self.keys.shared_secret(&self.params.group, h);
+        (ORACLE_MESSAGE.to_vec(), secret_mac(&secret))
+    }
+}
+
+/// An element of order r: a random element raised to (p - 1) / r, retried
+/// until it isn't 1
+fn element_of_order(p: &BigUint, r: u64) -> BigUint {
+    let exponent = (p - 1u32) / r;
+    loop {
+        let h = modexp(&random_range(&BigUint::from(2u32), p), &exponent, p);
+        if !h.is_one() {
+            return h;
+        }
+    }
+}
+
+/// Small-subgroup confinement (cryptopals 57). For each prime r below `bound`
+/// dividing j, an element h of order r makes Bob's secret h^x = h^(x mod r),
+/// and brute-forcing r MACs finds x mod r. Stops once the moduli multiply past
+/// q and returns (x mod M, M), or what it has if j runs out of small factors.
+pub fn subgroup_confinement<F>(params: &DhSubgroup, bound: u64, mut oracle: F) -> (BigUint, BigUint)
+where
+    F: FnMut(&BigUint) -> (Vec<u8>, Vec<u8>),
+{
+    let p = &params.group.p;
+    let mut residues = vec![BigUint::zero()];
+    let mut moduli = vec![BigUint::one()];
+    let mut product = BigUint::one();
+
+    for r in small_factors(&params.cofactor(), bound) {
+        if product > params.q {
+            break;
+        }
+
+        let h = element_of_order(p, r);
+        let (message, mac) = oracle(&h);
+        if message != ORACLE_MESSAGE {
+            continue;
+        }
+
+        let mut secret = BigUint::one();
+        for k in 0..r {
+            if secret_mac(&secret) == mac {
+                residues.push(BigUint::from(k));
+                moduli.push(BigUint::from(r));
+                product *= r;
+                break;
+            }
+            secret = secret * &h % p;
+        }
+    }
+
+    crt(&residues, &moduli).unwrap()
+}
+
+/// Recovers Bob's private key from his public key y (cryptopals 58). The
+/// confinement gives x = n mod M; writing x = n + mM, y * g^-n = (g^M)^m with
+/// m in [0, q / M], which the kangaroo finds when q / M is small enough.
+pub fn recover_private_key<F>(params: &DhSubgroup, public: &BigUint, bound: u64, oracle: F) -> Option<BigUint>
+where
+    F: FnMut(&BigUint) -> (Vec<u8>, Vec<u8>),
+{
+    let DhGroup { p, g } = &params.group;
+    let (n, modulus) = subgroup_confinement(params, bound, oracle);
+    if modulus > params.q {
+        return Some(n % &params.q);
+    }
+
+    let stride = DhGroup::new(p.clone(), modexp(g, &modulus, p));
+    let target = public * modinv(&modexp(g, &n, p), p)? % p;
+    let m = kangaroo(&stride, &target, &BigUint::zero(), &(&params.q / &modulus))?;
+
+    Some(n + m * modulus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subgroup_confinement() {
+        let params = DhSubgroup::cryptopals_57();
+        assert!(modexp(&params.group.g, &params.q, &params.group.p).is_one());
+
+        let mut oracle = MacOracle::new(params.clone());
+        let public = oracle.public_key().clone();
+
+        let x = recover_private_key(&params, &public, 1 << 16, |h| oracle.respond(h)).unwrap();
+        assert_eq!(modexp(&params.group.g, &x, &params.group.p), public);
+    }
+
+    #[test]
+    fn test_kangaroo_interval() {
+        let params = DhSubgroup::cryptopals_58();
+        let x = BigUint::from(705485u32);
+        let y = modexp(&params.group.g, &x, &params.group.p);
+
+        assert_eq!(kangaroo(&params.group, &y, &BigUint::zero(), &BigUint::from(1u32 << 20)), Some(x));
+    }
+
+    #[test]
+    fn test_confinement_then_kangaroo() {
+        let params = DhSubgroup::cryptopals_58();
+        let mut oracle = MacOracle::new(params.clone());
+        let public = oracle.public_key().clone();
+
+        let x = recover_private_key(&params, &public, 1 << 16, |h| oracle.respond(h)).unwrap();
+        assert_eq!(modexp(&params.group.g, &x, &params.group.p), public);
+    }
+}
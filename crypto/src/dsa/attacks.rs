@@ -0,0 +1,206 @@
+// crypto/src/dsa/attacks.rs
+use std::collections::HashMap;
+use std::{fs, io};
+
+use crate::bigint::{from_hex, mod_sub, modexp, modinv, random_range, BigUint, One, Zero};
+use crate::common::DsaError;
+
+use super::{DsaParams, DsaPublicKey, DsaSignature};
+
+/// x = (s*k - H(m)) / r mod q, given the nonce that produced (r, s)
+pub fn recover_private_key(params: &DsaParams, hash: &BigUint, signature: &DsaSignature, k: &BigUint) -> Option<BigUint> {
+    let q = &params.q;
+    let r_inverse = modinv(&signature.r, q)?;
+    let sk = (&signature.s * k) % q;
+    Some((mod_sub(&sk, &(hash % q), q) * r_inverse) % q)
+}
+
+/// Tries every k up to max_k, stepping g^k one multiplication at a time and only
+/// deriving x when r matches. The candidate is confirmed against y before returning.
+pub fn brute_force_nonce(public: &DsaPublicKey, hash: &BigUint, signature: &DsaSignature, max_k: u64) -> Option<(BigUint, BigUint)> {
+    let DsaParams { p, q, g } = &public.params;
+    let mut g_to_the_k = BigUint::one();
+
+    for k in 1..=max_k {
+        g_to_the_k = (g_to_the_k * g) % p;
+        if &g_to_the_k % q != signature.r {
+            continue;
+        }
+
+        let k = BigUint::from(k);
+        if let Some(x) = recover_private_key(&public.params, hash, signature, &k)
+            && modexp(g, &x, p) == public.y
+        {
+            return Some((k, x));
+        }
+    }
+
+    None
+}
+
+/// One record from a cryptopals-44 style file: the message, its signature and SHA-1
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedMessage {
+    pub message: String,
+    pub signature: DsaSignature,
+    pub hash: BigUint,
+}
+
+/// Records are four lines each: `msg:`, `s:` and `r:` in decimal, then `m:` in hex
+pub fn parse_signed_messages(input: &str) -> Result<Vec<SignedMessage>, DsaError> {
+    let lines: Vec<&str> = input.lines().filter(|line| !line.trim().is_empty()).collect();
+    if !lines.len().is_multiple_of(4) {
+        return Err(DsaError::InvalidFormat(format!("expected records of 4 lines, got {} lines", lines.len())));
+    }
+
+    let field = |line: &str, name: &str| -> Result<String, DsaError> {
+        line.strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .map(|value| value.strip_prefix(' ').unwrap_or(value).to_string())
+            .ok_or_else(|| DsaError::InvalidFormat(format!("expected '{}:' in {:?}", name, line)))
+    };
+    let decimal = |value: String| -> Result<BigUint, DsaError> {
+        value.trim().parse().map_err(|_| DsaError::InvalidFormat(format!("invalid integer {:?}", value)))
+    };
+
+    lines.chunks(4)
+        .map(|record| {
+            let message = field(record[0], "msg")?;
+            let s = decimal(field(record[1], "s")?)?;
+            let r = decimal(field(record[2], "r")?)?;
+            let m = field(record[3], "m")?;
+            let hash = from_hex(&m).map_err(|_| DsaError::InvalidFormat(format!("invalid hash {:?}", m)))?;
+
+            Ok(SignedMessage { message, signature: DsaSignature { r, s }, hash })
+        })
+        .collect()
+}
+
+pub fn load_signed_messages(path: &str) -> io::Result<Vec<SignedMessage>> {
+    let content = fs::read_to_string(path)?;
+    parse_signed_messages(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", e)))
+}
+
+/// Pairs of indices whose signatures share r, and therefore (almost certainly) k
+pub fn find_repeated_nonces(messages: &[SignedMessage]) -> Vec<(usize, usize)> {
+    let mut first_seen: HashMap<&BigUint, usize> = HashMap::new();
+    let mut pairs = Vec::new();
+
+    for (i, message) in messages.iter().enumerate() {
+        match first_seen.get(&message.signature.r) {
+            Some(&j) => pairs.push((j, i)),
+            None => {
+                first_seen.insert(&message.signature.r, i);
+            }
+        }
+    }
+
+    pairs
+}
+
+/// k = (m1 - m2) / (s1 - s2) mod q, then x from either signature
+pub fn recover_from_repeated_nonce(params: &DsaParams, a: &SignedMessage, b: &SignedMessage) -> Option<BigUint> {
+    let q = &params.q;
+    if a.signature.r != b.signature.r {
+        return None;
+    }
+
+    let numerator = mod_sub(&(&a.hash % q), &(&b.hash % q), q);
+    let denominator = mod_sub(&a.signature.s, &b.signature.s, q);
+    let k = (numerator * modinv(&denominator, q)?) % q;
+
+    recover_private_key(params, &a.hash, &a.signature, &k)
+}
+
+/// With g = 0 every honest r is 0, and a lenient verifier computes v = 0 for any
+/// message, so (0, anything) verifies everywhere
+pub fn forge_with_g_zero(public: &DsaPublicKey) -> DsaSignature {
+    DsaSignature {
+        r: BigUint::zero(),
+        s: random_range(&BigUint::one(), &public.params.q),
+    }
+}
+
+/// With g = p + 1 = 1 mod p the g^u1 term vanishes: r = (y^z mod p) mod q and
+/// s = r / z mod q make u2 = z, so the signature verifies for any message
+pub fn forge_with_g_p_plus_one(public: &DsaPublicKey, z: &BigUint) -> Option<DsaSignature> {
+    let DsaParams { p, q, .. } = &public.params;
+    let r = modexp(&public.y, z, p) % q;
+    let s = (&r * modinv(z, q)?) % q;
+    Some(DsaSignature { r, s })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsa::{message_hash, DsaKeyPair};
+    use crate::hash::{sha1, DigestAlgorithm};
+    use serialize::to_hex;
+
+    const CHALLENGE44: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../data/set06/challenge44.txt");
+
+    fn challenge43_key() -> DsaPublicKey {
+        let y = from_hex(
+            "84ad4719d044495496a3201c8ff484feb45b962e7302e56a392aee4abab3e4bdebf2955b4736012f
+             21a08084056b19bcd7fee56048e004e44984e2f411788efdc837a0d2e5abb7b555039fd243ac01f0
+             fb2ed1dec568280ce678e931868d23eb095fde9d3779191b8c0299d6e07bbb283e6633451e535c45
+             513b2d33c99ea17"
+        ).unwrap();
+        DsaPublicKey { params: DsaParams::standard(), y }
+    }
+
+    #[test]
+    fn test_brute_force_small_nonce() {
+        let public = challenge43_key();
+        let message = b"For those that envy a MC it can be hazardous to your health\n\
+                        So be friendly, a matter of life and death, just like a etch-a-sketch\n";
+        let signature = DsaSignature {
+            r: "548099063082341131477253921760299949438196259240".parse().unwrap(),
+            s: "857042759984254168557880549501802188789837994940".parse().unwrap(),
+        };
+        let hash = message_hash(message, DigestAlgorithm::Sha1, &public.params.q);
+        assert!(public.verify(message, &signature, DigestAlgorithm::Sha1));
+
+        let (k, x) = brute_force_nonce(&public, &hash, &signature, 1 << 16).unwrap();
+        assert_eq!(k, BigUint::from(16575u32));
+        assert_eq!(to_hex(&sha1(crate::bigint::to_hex(&x).as_bytes())), "0954edd5e0afe5542a4adf012611a91912a3ec16");
+    }
+
+    #[test]
+    fn test_repeated_nonce_recovery() {
+        let params = DsaParams::standard();
+        let messages = load_signed_messages(CHALLENGE44).unwrap();
+        assert_eq!(messages.len(), 12);
+        for message in &messages {
+            assert_eq!(message.hash, from_hex(&to_hex(&sha1(message.message.as_bytes()))).unwrap());
+        }
+
+        let pairs = find_repeated_nonces(&messages);
+        assert!(!pairs.is_empty());
+
+        for (i, j) in pairs {
+            let x = recover_from_repeated_nonce(&params, &messages[i], &messages[j]).unwrap();
+            assert_eq!(to_hex(&sha1(crate::bigint::to_hex(&x).as_bytes())), "a452879f5107824695343632b48228032e9cbe5b");
+        }
+    }
+
+    #[test]
+    fn test_parameter_tampering() {
+        let keys = DsaKeyPair::generate(&DsaParams::standard());
+        let digest = DigestAlgorithm::Sha256;
+
+        let mut zero = keys.public.clone();
+        zero.params.g = BigUint::zero();
+        let forged = forge_with_g_zero(&zero);
+        assert!(zero.verify_lenient(b"Hello, world", &forged, digest));
+        assert!(zero.verify_lenient(b"Goodbye, world", &forged, digest));
+        assert!(!zero.verify(b"Hello, world", &forged, digest));
+
+        let mut magic = keys.public.clone();
+        magic.params.g = &magic.params.p + 1u32;
+        let forged = forge_with_g_p_plus_one(&magic, &BigUint::from(42u32)).unwrap();
+        assert!(magic.verify_lenient(b"Hello, world", &forged, digest));
+        assert!(magic.verify_lenient(b"Goodbye, world", &forged, digest));
+        assert!(!magic.verify(b"Hello, world", &forged, digest));
+    }
+}
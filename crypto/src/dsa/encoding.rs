@@ -0,0 +1,74 @@
+// crypto/src/dsa/encoding.rs
+use serialize::{Der, Pem, SerializeError, SubjectPublicKeyInfo};
+
+use crate::bigint::{from_der, to_der};
+
+use super::{DsaParams, DsaPublicKey};
+
+/// id-dsa, 1.2.840.10040.4.1 (RFC 3279, section 2.3.2)
+pub const ID_DSA: &[u64] = &[1, 2, 840, 10040, 4, 1];
+
+impl DsaParams {
+    /// Dss-Parms: SEQUENCE { p, q, g }
+    pub fn to_der(&self) -> Der {
+        Der::Sequence(vec![to_der(&self.p), to_der(&self.q), to_der(&self.g)])
+    }
+
+    pub fn from_der(der: &Der) -> Result<Self, SerializeError> {
+        match der.as_sequence()? {
+            [p, q, g] => Ok(Self::new(from_der(p)?, from_der(q)?, from_der(g)?)),
+            _ => Err(SerializeError::InvalidData("expected Dss-Parms { p, q, g }".to_string())),
+        }
+    }
+}
+
+impl DsaPublicKey {
+    /// SubjectPublicKeyInfo with the domain parameters inline and y as an INTEGER
+    pub fn to_spki_der(&self) -> Vec<u8> {
+        SubjectPublicKeyInfo {
+            algorithm: ID_DSA.to_vec(),
+            parameters: Some(self.params.to_der()),
+            public_key: to_der(&self.y).to_der(),
+        }
+        .to_der()
+    }
+
+    pub fn from_spki_der(bytes: &[u8]) -> Result<Self, SerializeError> {
+        let info = SubjectPublicKeyInfo::from_der(bytes)?;
+        let (ID_DSA, Some(parameters)) = (info.algorithm.as_slice(), &info.parameters) else {
+            return Err(SerializeError::InvalidData("expected id-dsa key with parameters".to_string()));
+        };
+
+        Ok(Self {
+            params: DsaParams::from_der(parameters)?,
+            y: from_der(&Der::from_der(&info.public_key)?)?,
+        })
+    }
+
+    pub fn to_spki_pem(&self) -> String {
+        Pem::new("PUBLIC KEY", self.to_spki_der()).encode()
+    }
+
+    pub fn from_spki_pem(text: &str) -> Result<Self, SerializeError> {
+        Self::from_spki_der(&Pem::parse(text)?.expect("PUBLIC KEY")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsa::DsaKeyPair;
+
+    #[test]
+    fn test_openssl_fixture_round_trip() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../data/keys/dsa1024_spki.pem");
+        let pem = std::fs::read_to_string(path).unwrap();
+
+        let public = DsaPublicKey::from_spki_pem(&pem).unwrap();
+        assert!(public.params.validate().is_ok());
+        assert_eq!(public.to_spki_pem(), pem);
+
+        let keys = DsaKeyPair::generate(&public.params);
+        assert_eq!(DsaPublicKey::from_spki_der(&keys.public.to_spki_der()).unwrap(), keys.public);
+    }
+}
@@ -0,0 +1,180 @@
+// crypto/src/dsa/mod.rs
+pub mod attacks;
+pub mod encoding;
+
+pub use attacks::{
+    brute_force_nonce, find_repeated_nonces, forge_with_g_p_plus_one, forge_with_g_zero,
+    load_signed_messages, parse_signed_messages, recover_from_repeated_nonce, recover_private_key,
+    SignedMessage,
+};
+
+use crate::bigint::{from_bytes, from_hex, modexp, modinv, random_range, BigUint, One, Zero};
+use crate::common::DsaError;
+use crate::hash::DigestAlgorithm;
+
+const STANDARD_P: &str = "
+    800000000000000089e1855218a0e7dac38136ffafa72eda7859f2171e25e65eac698c1702578b07
+    dc2a1076da241c76c62d374d8389ea5aeffd3226a0530cc565f3bf6b50929139ebeac04f48c3c84a
+    fb796d61e5a4f9a8fda812ab59494232c7d2b4deb50aa18ee9e132bfa85ac4374d7f9091abc3d015
+    efc871a584471bb1";
+const STANDARD_Q: &str = "f4f47f05794b256174bba6e9b396a7707e563c5b";
+const STANDARD_G: &str = "
+    5958c9d3898b224b12672c0b98e06c60df923cb8bc999d119458fef538b8fa4046c8db53039db620
+    c094c9fa077ef389b5322a559946a71903f990f1f7e0e025e2d7f7cf494aff1a0470f5b64c36b625
+    a097f1651fe775323556fe00b3608c887892878480e99041be601a62166ca6894bdd41a7054ec89f
+    756ba9fc95302291";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DsaParams {
+    pub p: BigUint,
+    pub q: BigUint,
+    pub g: BigUint,
+}
+
+impl DsaParams {
+    pub fn new(p: BigUint, q: BigUint, g: BigUint) -> Self {
+        Self { p, q, g }
+    }
+
+    /// The 1024/160-bit group used by the cryptopals DSA challenges
+    pub fn standard() -> Self {
+        Self {
+            p: from_hex(STANDARD_P).unwrap(),
+            q: from_hex(STANDARD_Q).unwrap(),
+            g: from_hex(STANDARD_G).unwrap(),
+        }
+    }
+
+    /// g must generate the order-q subgroup: 1 < g < p and g^q = 1 mod p
+    pub fn validate(&self) -> Result<(), DsaError> {
+        let one = BigUint::one();
+        if self.p <= one
+            || self.q.is_zero()
+            || !((&self.p - 1u32) % &self.q).is_zero()
+            || self.g <= one
+            || self.g >= self.p
+            || !modexp(&self.g, &self.q, &self.p).is_one()
+        {
+            return Err(DsaError::InvalidParameters);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DsaSignature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DsaPublicKey {
+    pub params: DsaParams,
+    pub y: BigUint,
+}
+
+#[derive(Debug, Clone)]
+pub struct DsaKeyPair {
+    pub public: DsaPublicKey,
+    x: BigUint,
+}
+
+impl DsaKeyPair {
+    pub fn generate(params: &DsaParams) -> Self {
+        let x = random_range(&BigUint::one(), &params.q);
+        Self::from_private(params, x)
+    }
+
+    pub fn from_private(params: &DsaParams, x: BigUint) -> Self {
+        let y = modexp(&params.g, &x, &params.p);
+        Self {
+            public: DsaPublicKey { params: params.clone(), y },
+            x,
+        }
+    }
+
+    pub fn private_key(&self) -> &BigUint {
+        &self.x
+    }
+
+    pub fn sign(&self, message: &[u8], digest: DigestAlgorithm) -> Result<DsaSignature, DsaError> {
+        self.public.params.validate()?;
+        let q = &self.public.params.q;
+
+        loop {
+            let k = random_range(&BigUint::one(), q);
+            match self.sign_with_nonce(message, digest, &k) {
+                Err(DsaError::InvalidNonce) => continue,
+                result => return result,
+            }
+        }
+    }
+
+    /// Sign with a caller-chosen k, which is exactly what the nonce attacks exploit
+    pub fn sign_with_nonce(&self, message: &[u8], digest: DigestAlgorithm, k: &BigUint) -> Result<DsaSignature, DsaError> {
+        let DsaParams { p, q, g } = &self.public.params;
+
+        let r = modexp(g, k, p) % q;
+        let k_inverse = modinv(k, q).ok_or(DsaError::InvalidNonce)?;
+        let s = (k_inverse * (message_hash(message, digest, q) + &self.x * &r)) % q;
+
+        if r.is_zero() || s.is_zero() {
+            return Err(DsaError::InvalidNonce);
+        }
+
+        Ok(DsaSignature { r, s })
+    }
+}
+
+impl DsaPublicKey {
+    /// Full FIPS 186 verification: checks the group as well as 0 < r, s < q
+    pub fn verify(&self, message: &[u8], signature: &DsaSignature, digest: DigestAlgorithm) -> bool {
+        let q = &self.params.q;
+        let in_range = |v: &BigUint| !v.is_zero() && v < q;
+
+        self.params.validate().is_ok()
+            && in_range(&signature.r)
+            && in_range(&signature.s)
+            && self.verify_lenient(message, signature, digest)
+    }
+
+    /// Only the core equation, trusting whatever parameters and values it's given
+    pub fn verify_lenient(&self, message: &[u8], signature: &DsaSignature, digest: DigestAlgorithm) -> bool {
+        let DsaParams { p, q, g } = &self.params;
+
+        let Some(w) = modinv(&signature.s, q) else {
+            return false;
+        };
+        let u1 = (message_hash(message, digest, q) * &w) % q;
+        let u2 = (&signature.r * &w) % q;
+        let v = ((modexp(g, &u1, p) * modexp(&self.y, &u2, p)) % p) % q;
+
+        v == signature.r
+    }
+}
+
+/// H(m) as an integer, keeping only the leftmost bits when the hash is wider than q
+pub fn message_hash(message: &[u8], digest: DigestAlgorithm, q: &BigUint) -> BigUint {
+    let hash = digest.hash(message);
+    let h = from_bytes(&hash);
+    let excess = (hash.len() as u64 * 8).saturating_sub(q.bits());
+    h >> excess
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let params = DsaParams::standard();
+        assert!(params.validate().is_ok());
+
+        let keys = DsaKeyPair::generate(&params);
+        for digest in [DigestAlgorithm::Sha1, DigestAlgorithm::Sha256] {
+            let signature = keys.sign(b"hello, world", digest).unwrap();
+            assert!(keys.public.verify(b"hello, world", &signature, digest));
+            assert!(!keys.public.verify(b"goodbye, world", &signature, digest));
+        }
+    }
+}
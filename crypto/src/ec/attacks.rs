@@ -0,0 +1,255 @@
+// crypto/src/ec/attacks.rs
+use crate::bigint::{crt, small_factors, BigUint, One, Zero};
+use crate::dh::secret_mac;
+use crate::kangaroo::kangaroo;
+
+use super::{EcGroup, EcdhKeyPair, MontgomeryGroup, Point, WeierstrassCurve, XOnlyKeyPair};
+
+/// A point secret is MACed as the big-endian x bytes followed by y's
+fn point_mac(point: &Point) -> Vec<u8> {
+    match point {
+        Point::Infinity => secret_mac(&BigUint::zero()),
+        Point::Affine { x, y } => secret_mac(&BigUint::from_bytes_be(&[x.to_bytes_be(), y.to_bytes_be()].concat())),
+    }
+}
+
+/// Bob in a Weierstrass ECDH exchange who MACs a message with whatever
+/// secret comes out of the peer's point, without validating it
+pub struct EcdhOracle {
+    group: EcGroup,
+    keys: EcdhKeyPair,
+    pub queries: usize,
+}
+
+impl EcdhOracle {
+    pub fn new(group: EcGroup) -> Self {
+        let keys = EcdhKeyPair::generate(&group);
+        Self { group, keys, queries: 0 }
+    }
+
+    pub fn public_key(&self) -> &Point {
+        &self.keys.public
+    }
+
+    pub fn respond(&mut self, point: &Point) -> Vec<u8> {
+        self.queries += 1;
+        point_mac(&self.keys.shared_secret(&self.group, point))
+    }
+}
+
+/// The same Bob on an x-only Montgomery ladder
+pub struct XOnlyOracle {
+    group: MontgomeryGroup,
+    keys: XOnlyKeyPair,
+    pub queries: usize,
+}
+
+impl XOnlyOracle {
+    pub fn new(group: MontgomeryGroup) -> Self {
+        let keys = XOnlyKeyPair::generate(&group);
+        Self { group, keys, queries: 0 }
+    }
+
+    pub fn public_key(&self) -> &BigUint {
+        &self.keys.public
+    }
+
+    pub fn respond(&mut self, u: &BigUint) -> Vec<u8> {
+        self.queries += 1;
+        secret_mac(&self.keys.shared_secret(&self.group, u))
+    }
+}
+
+/// A point of prime order r on a curve of the given order. None if random
+/// points keep landing on the identity, as they always do when the r-torsion
+/// isn't cyclic (e.g. r = 2 on a curve with full 2-torsion)
+fn point_of_order(curve: &WeierstrassCurve, order: &BigUint, r: u64) -> Option<Point> {
+    let cofactor = order / r;
+    (0..32)
+        .map(|_| curve.scalar_mul(&curve.random_point(), &cofactor))
+        .find(|point| *point != Point::Infinity)
+}
+
+/// Invalid-curve attack (cryptopals 59). The addition law never uses b, so a
+/// point on y^2 = x^3 + ax + b' goes through Bob's arithmetic on that other
+/// curve. Picking b' whose group order has small factors r, a point of order r
+/// confines the secret to r possibilities, and the MAC reveals which: x mod r.
+/// CRT combines residues until their product exceeds the subgroup order.
+///
+/// `curves` lists (b', order of y^2 = x^3 + ax + b'). Returns None if the small
+/// factors below `bound` don't cover the group order.
+pub fn invalid_curve_attack<F>(group: &EcGroup, curves: &[(BigUint, BigUint)], bound: u64, mut oracle: F) -> Option<BigUint>
+where
+    F: FnMut(&Point) -> Vec<u8>,
+{
+    let mut residues = Vec::new();
+    let mut moduli: Vec<BigUint> = Vec::new();
+    let mut product = BigUint::one();
+
+    for (b, order) in curves {
+        let curve = WeierstrassCurve::new(group.curve.p.clone(), group.curve.a.clone(), b.clone());
+
+        for r in small_factors(order, bound) {
+            if product > group.n {
+                break;
+            }
+            if moduli.contains(&BigUint::from(r)) {
+                continue;
+            }
+
+            let Some(h) = point_of_order(&curve, order, r) else {
+                continue;
+            };
+            let mac = oracle(&h);
+
+            let mut multiple = Point::Infinity;
+            for k in 0..r {
+                if point_mac(&multiple) == mac {
+                    residues.push(BigUint::from(k));
+                    moduli.push(BigUint::from(r));
+                    product *= r;
+                    break;
+                }
+                multiple = curve.add(&multiple, &h);
+            }
+        }
+    }
+
+    if product <= group.n {
+        return None;
+    }
+    let (x, _) = crt(&residues, &moduli)?;
+    Some(x)
+}
+
+/// k such that u(kP) = u(P's target) for k in 0..=r/2, from a MAC of it
+fn x_only_residue(group: &MontgomeryGroup, u: &BigUint, r: u64, mac: &[u8]) -> Option<u64> {
+    if secret_mac(&BigUint::zero()) == mac {
+        return Some(0);
+    }
+    group.curve.multiples(u)
+        .take(r as usize / 2)
+        .position(|multiple| secret_mac(&multiple) == mac)
+        .map(|i| i as u64 + 1)
+}
+
+/// A twist u-coordinate of exact order `order`, whose prime factors are `primes`
+fn twist_point_of_order(group: &MontgomeryGroup, order: &BigUint, primes: &[BigUint]) -> BigUint {
+    let cofactor = group.twist_order() / order;
+    loop {
+        let u = group.curve.ladder(&group.curve.random_twist_u(), &cofactor);
+        if primes.iter().all(|r| !group.curve.ladder(&u, &(order / r)).is_zero()) {
+            return u;
+        }
+    }
+}
+
+/// Twist attack on an x-only ladder (cryptopals 60). u-coordinates on the
+/// quadratic twist pass through the ladder, so points of small order r on the
+/// twist leak x mod r, but only up to sign since u(kP) = u(-kP). Each new
+/// residue's sign is fixed relative to the ones before it with a query on a
+/// point whose order is the product of all the moduli so far, leaving
+/// x = ±n mod M. Pollard's kangaroo on the real curve then finds the rest:
+/// x = ±n + mM with m below n/M, trying both lifts of Bob's public u.
+pub fn twist_attack<F>(group: &MontgomeryGroup, public: &BigUint, bound: u64, mut oracle: F) -> Option<BigUint>
+where
+    F: FnMut(&BigUint) -> Vec<u8>,
+{
+    let twist_order = group.twist_order();
+    let mut residue = BigUint::zero();
+    let mut modulus = BigUint::one();
+    let mut primes: Vec<BigUint> = Vec::new();
+
+    for r in small_factors(&twist_order, bound).into_iter().filter(|&r| r > 2) {
+        let big_r = BigUint::from(r);
+        let h = twist_point_of_order(group, &big_r, std::slice::from_ref(&big_r));
+        let k = x_only_residue(group, &h, r, &oracle(&h))?;
+
+        if primes.is_empty() || k == 0 {
+            residue = crt(&[residue, BigUint::from(k)], &[modulus.clone(), big_r.clone()])?.0;
+        } else {
+            let combined_order = &modulus * r;
+            let mut all_primes = primes.clone();
+            all_primes.push(big_r.clone());
+            let h = twist_point_of_order(group, &combined_order, &all_primes);
+            let mac = oracle(&h);
+
+            residue = [k, r - k].into_iter()
+                .map(|k| crt(&[residue.clone(), BigUint::from(k)], &[modulus.clone(), big_r.clone()]).unwrap().0)
+                .find(|candidate| secret_mac(&group.curve.ladder(&h, candidate)) == mac)?;
+        }
+
+        modulus *= r;
+        primes.push(big_r);
+    }
+
+    let weierstrass = group.to_weierstrass();
+    let target = group.curve.lift_u(public)?;
+    let stride = EcGroup::new(weierstrass.curve.clone(), weierstrass.multiply(&modulus), group.n.clone());
+    let upper = &group.n / &modulus + 1u32;
+
+    for y in [weierstrass.curve.negate(&target), target] {
+        for offset in [residue.clone(), (&modulus - &residue) % &modulus] {
+            let shifted = weierstrass.curve.add(&y, &weierstrass.curve.negate(&weierstrass.multiply(&offset)));
+            if let Some(m) = kangaroo(&stride, &shifted, &BigUint::zero(), &upper) {
+                let x = (&offset + m * &modulus) % &group.n;
+                if group.multiply(&x) == *public {
+                    return Some(x);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ec::MontgomeryCurve;
+
+    /// The three invalid curves from cryptopals 59 with their group orders
+    fn invalid_curves() -> Vec<(BigUint, BigUint)> {
+        [
+            (210u32, "233970423115425145550826547352470124412"),
+            (504, "233970423115425145544350131142039591210"),
+            (727, "233970423115425145545378039958152057148"),
+        ]
+        .iter()
+        .map(|(b, order)| (BigUint::from(*b), order.parse().unwrap()))
+        .collect()
+    }
+
+    /// A 40-bit Montgomery curve with order 4q and a twist whose small factors
+    /// (3^2 * 5 * 11 * 83) leave about 24 bits for the kangaroo
+    fn small_group() -> MontgomeryGroup {
+        let curve = MontgomeryCurve::new(
+            BigUint::from(1040772936787u64),
+            BigUint::from(689001003249u64),
+            BigUint::one(),
+        );
+        MontgomeryGroup::new(curve, BigUint::from(495773780643u64), BigUint::from(260192985889u64), BigUint::from(4u32))
+    }
+
+    #[test]
+    fn test_invalid_curve_attack() {
+        let group = EcGroup::cryptopals();
+        let mut oracle = EcdhOracle::new(group.clone());
+        let public = oracle.public_key().clone();
+
+        let x = invalid_curve_attack(&group, &invalid_curves(), 1 << 16, |point| oracle.respond(point)).unwrap();
+        assert_eq!(group.multiply(&x), public);
+    }
+
+    #[test]
+    fn test_twist_attack() {
+        let group = small_group();
+        assert!(small_factors(&group.twist_order(), 1 << 10).contains(&83));
+
+        let mut oracle = XOnlyOracle::new(group.clone());
+        let public = oracle.public_key().clone();
+
+        let x = twist_attack(&group, &public, 1 << 10, |u| oracle.respond(u)).unwrap();
+        assert_eq!(group.multiply(&x), public);
+    }
+}
@@ -0,0 +1,91 @@
+// crypto/src/ec/ecdh.rs
+use crate::bigint::BigUint;
+use crate::common::EcError;
+
+use super::{EcGroup, MontgomeryGroup, Point};
+
+#[derive(Debug, Clone)]
+pub struct EcdhKeyPair {
+    private: BigUint,
+    pub public: Point,
+}
+
+impl EcdhKeyPair {
+    pub fn generate(group: &EcGroup) -> Self {
+        Self::from_private(group, group.random_scalar())
+    }
+
+    pub fn from_private(group: &EcGroup, private: BigUint) -> Self {
+        let public = group.multiply(&private);
+        Self { private, public }
+    }
+
+    pub fn private_key(&self) -> &BigUint {
+        &self.private
+    }
+
+    /// Multiplies whatever point it's given, the mistake the invalid-curve attack exploits
+    pub fn shared_secret(&self, group: &EcGroup, other_public: &Point) -> Point {
+        group.curve.scalar_mul(other_public, &self.private)
+    }
+
+    pub fn shared_secret_checked(&self, group: &EcGroup, other_public: &Point) -> Result<Point, EcError> {
+        if !group.validate(other_public) {
+            return Err(EcError::InvalidPoint);
+        }
+        Ok(self.shared_secret(group, other_public))
+    }
+}
+
+/// ECDH on a Montgomery curve exchanging only u-coordinates
+#[derive(Debug, Clone)]
+pub struct XOnlyKeyPair {
+    private: BigUint,
+    pub public: BigUint,
+}
+
+impl XOnlyKeyPair {
+    pub fn generate(group: &MontgomeryGroup) -> Self {
+        Self::from_private(group, group.to_weierstrass().random_scalar())
+    }
+
+    pub fn from_private(group: &MontgomeryGroup, private: BigUint) -> Self {
+        let public = group.multiply(&private);
+        Self { private, public }
+    }
+
+    pub fn private_key(&self) -> &BigUint {
+        &self.private
+    }
+
+    /// Runs the ladder on any u, including ones that are only on the twist
+    pub fn shared_secret(&self, group: &MontgomeryGroup, other_public: &BigUint) -> BigUint {
+        group.curve.ladder(other_public, &self.private)
+    }
+
+    pub fn shared_secret_checked(&self, group: &MontgomeryGroup, other_public: &BigUint) -> Result<BigUint, EcError> {
+        if group.curve.on_twist(other_public) {
+            return Err(EcError::InvalidPoint);
+        }
+        Ok(self.shared_secret(group, other_public))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_agreement() {
+        let group = EcGroup::cryptopals();
+        let alice = EcdhKeyPair::generate(&group);
+        let bob = EcdhKeyPair::generate(&group);
+        assert_eq!(alice.shared_secret(&group, &bob.public), bob.shared_secret_checked(&group, &alice.public).unwrap());
+
+        let group = MontgomeryGroup::cryptopals();
+        let alice = XOnlyKeyPair::generate(&group);
+        let bob = XOnlyKeyPair::generate(&group);
+        assert_eq!(alice.shared_secret(&group, &bob.public), bob.shared_secret_checked(&group, &alice.public).unwrap());
+        assert!(bob.shared_secret_checked(&group, &group.curve.random_twist_u()).is_err());
+    }
+}
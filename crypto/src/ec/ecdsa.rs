@@ -0,0 +1,84 @@
+// crypto/src/ec/ecdsa.rs
+use crate::bigint::{modinv, BigUint, Zero};
+use crate::dsa::message_hash;
+use crate::hash::DigestAlgorithm;
+
+use super::{EcGroup, Point};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EcdsaSignature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+#[derive(Debug, Clone)]
+pub struct EcdsaKeyPair {
+    d: BigUint,
+    pub public: Point,
+}
+
+impl EcdsaKeyPair {
+    pub fn generate(group: &EcGroup) -> Self {
+        Self::from_private(group, group.random_scalar())
+    }
+
+    pub fn from_private(group: &EcGroup, d: BigUint) -> Self {
+        let public = group.multiply(&d);
+        Self { d, public }
+    }
+
+    pub fn private_key(&self) -> &BigUint {
+        &self.d
+    }
+
+    /// r = x(kG) mod n, s = (H(m) + d*r) / k mod n, with the hash truncated to n's length
+    pub fn sign(&self, group: &EcGroup, message: &[u8], digest: DigestAlgorithm) -> EcdsaSignature {
+        let n = &group.n;
+        let hash = message_hash(message, digest, n);
+
+        loop {
+            let k = group.random_scalar();
+            let Some(x) = group.multiply(&k).x().cloned() else {
+                continue;
+            };
+
+            let r = x % n;
+            let s = modinv(&k, n).unwrap() * (&hash + &self.d * &r) % n;
+            if !r.is_zero() && !s.is_zero() {
+                return EcdsaSignature { r, s };
+            }
+        }
+    }
+}
+
+pub fn verify(group: &EcGroup, public: &Point, message: &[u8], signature: &EcdsaSignature, digest: DigestAlgorithm) -> bool {
+    let n = &group.n;
+    let in_range = |v: &BigUint| !v.is_zero() && v < n;
+    if !in_range(&signature.r) || !in_range(&signature.s) || !group.validate(public) {
+        return false;
+    }
+
+    let w = modinv(&signature.s, n).unwrap();
+    let u1 = message_hash(message, digest, n) * &w % n;
+    let u2 = &signature.r * &w % n;
+    let point = group.curve.add(&group.multiply(&u1), &group.curve.scalar_mul(public, &u2));
+
+    point.x().is_some_and(|x| x % n == signature.r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let group = EcGroup::cryptopals();
+        let keys = EcdsaKeyPair::generate(&group);
+
+        for digest in [DigestAlgorithm::Sha1, DigestAlgorithm::Sha256] {
+            let signature = keys.sign(&group, b"hello, world", digest);
+            assert!(verify(&group, &keys.public, b"hello, world", &signature, digest));
+            assert!(!verify(&group, &keys.public, b"goodbye, world", &signature, digest));
+        }
+    }
+}
@@ -0,0 +1,225 @@
+// crypto/src/ec/mod.rs
+pub mod attacks;
+pub mod ecdh;
+pub mod ecdsa;
+pub mod montgomery;
+
+pub use attacks::{invalid_curve_attack, twist_attack, EcdhOracle, XOnlyOracle};
+pub use ecdh::{EcdhKeyPair, XOnlyKeyPair};
+pub use ecdsa::{EcdsaKeyPair, EcdsaSignature};
+pub use montgomery::{MontgomeryCurve, MontgomeryGroup};
+
+use crate::bigint::{mod_sub, modinv, random_below, sqrt_mod, BigUint, One, Zero};
+use crate::kangaroo::KangarooGroup;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Point {
+    Infinity,
+    Affine { x: BigUint, y: BigUint },
+}
+
+impl Point {
+    pub fn new(x: BigUint, y: BigUint) -> Self {
+        Point::Affine { x, y }
+    }
+
+    pub fn x(&self) -> Option<&BigUint> {
+        match self {
+            Point::Infinity => None,
+            Point::Affine { x, .. } => Some(x),
+        }
+    }
+}
+
+/// y^2 = x^3 + ax + b over GF(p)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeierstrassCurve {
+    pub p: BigUint,
+    pub a: BigUint,
+    pub b: BigUint,
+}
+
+impl WeierstrassCurve {
+    pub fn new(p: BigUint, a: BigUint, b: BigUint) -> Self {
+        Self { p, a, b }
+    }
+
+    fn rhs(&self, x: &BigUint) -> BigUint {
+        (x * x * x + &self.a * x + &self.b) % &self.p
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => true,
+            Point::Affine { x, y } => x < &self.p && y < &self.p && y * y % &self.p == self.rhs(x),
+        }
+    }
+
+    pub fn negate(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine { x, y } => Point::new(x.clone(), mod_sub(&BigUint::zero(), y, &self.p)),
+        }
+    }
+
+    /// The chord-and-tangent law. Nothing here checks that the inputs are on
+    /// this curve, and b never appears in the formulas, which is exactly what
+    /// the invalid-curve attack relies on. Coordinates are reduced mod p first,
+    /// and a vertical line through two off-curve points with the same x gives
+    /// the point at infinity rather than a panic.
+    pub fn add(&self, first: &Point, second: &Point) -> Point {
+        let p = &self.p;
+        let (x1, y1, x2, y2) = match (first, second) {
+            (Point::Infinity, other) | (other, Point::Infinity) => return other.clone(),
+            (Point::Affine { x: x1, y: y1 }, Point::Affine { x: x2, y: y2 }) => (x1 % p, y1 % p, x2 % p, y2 % p),
+        };
+
+        let (numerator, denominator) = if x1 != x2 {
+            (mod_sub(&y2, &y1, p), mod_sub(&x2, &x1, p))
+        } else if y1 == y2 {
+            ((BigUint::from(3u32) * &x1 * &x1 + &self.a) % p, &y1 * 2u32 % p)
+        } else {
+            return Point::Infinity;
+        };
+        let Some(inverse) = modinv(&denominator, p) else {
+            return Point::Infinity;
+        };
+        let slope = numerator * inverse % p;
+
+        let x3 = mod_sub(&(&slope * &slope), &(&x1 + &x2), p);
+        let y3 = mod_sub(&(&slope * mod_sub(&x1, &x3, p)), &y1, p);
+        Point::new(x3, y3)
+    }
+
+    pub fn double(&self, point: &Point) -> Point {
+        self.add(point, point)
+    }
+
+    /// Double-and-add, most significant bit first
+    pub fn scalar_mul(&self, point: &Point, k: &BigUint) -> Point {
+        let mut result = Point::Infinity;
+        for i in (0..k.bits()).rev() {
+            result = self.double(&result);
+            if k.bit(i) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
+
+    /// A point with the given x, if x^3 + ax + b is a square
+    pub fn lift_x(&self, x: &BigUint) -> Option<Point> {
+        let y = sqrt_mod(&self.rhs(x), &self.p)?;
+        Some(Point::new(x % &self.p, y))
+    }
+
+    pub fn random_point(&self) -> Point {
+        loop {
+            if let Some(point) = self.lift_x(&random_below(&self.p)) {
+                return point;
+            }
+        }
+    }
+}
+
+/// A curve, a base point and its (prime) order
+#[derive(Debug, Clone, PartialEq)]
+pub struct EcGroup {
+    pub curve: WeierstrassCurve,
+    pub g: Point,
+    pub n: BigUint,
+}
+
+impl EcGroup {
+    pub fn new(curve: WeierstrassCurve, g: Point, n: BigUint) -> Self {
+        Self { curve, g, n }
+    }
+
+    /// y^2 = x^3 - 95051x + 11279326 from cryptopals 59, whose base point has
+    /// prime order q and cofactor 8
+    pub fn cryptopals() -> Self {
+        let p: BigUint = "233970423115425145524320034830162017933".parse().unwrap();
+        let a = &p - 95051u32;
+        let curve = WeierstrassCurve::new(p, a, BigUint::from(11279326u32));
+        let g = Point::new(BigUint::from(182u32), "85518893674295321206118380980485522083".parse().unwrap());
+
+        Self { curve, g, n: "29246302889428143187362802287225875743".parse().unwrap() }
+    }
+
+    pub fn multiply(&self, k: &BigUint) -> Point {
+        self.curve.scalar_mul(&self.g, k)
+    }
+
+    /// What a careful peer checks before using a public point: on the curve,
+    /// not the identity, and in the prime-order subgroup
+    pub fn validate(&self, point: &Point) -> bool {
+        *point != Point::Infinity
+            && self.curve.contains(point)
+            && self.curve.scalar_mul(point, &self.n) == Point::Infinity
+    }
+
+    pub fn random_scalar(&self) -> BigUint {
+        random_below(&(&self.n - 1u32)) + BigUint::one()
+    }
+}
+
+impl KangarooGroup for EcGroup {
+    type Element = Point;
+
+    fn power(&self, k: &BigUint) -> Point {
+        self.multiply(k)
+    }
+
+    fn combine(&self, a: &Point, b: &Point) -> Point {
+        self.curve.add(a, b)
+    }
+
+    fn index(&self, element: &Point, k: u32) -> u32 {
+        element.x().map_or(0, |x| (x % k).try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kangaroo::kangaroo;
+
+    #[test]
+    fn test_group_law() {
+        let group = EcGroup::cryptopals();
+        assert!(group.validate(&group.g));
+
+        let a = group.random_scalar();
+        let b = group.random_scalar();
+        let sum = group.curve.add(&group.multiply(&a), &group.multiply(&b));
+        assert_eq!(sum, group.multiply(&((&a + &b) % &group.n)));
+        assert!(group.curve.contains(&sum));
+
+        assert_eq!(group.curve.add(&group.g, &group.curve.negate(&group.g)), Point::Infinity);
+        assert_eq!(group.multiply(&group.n), Point::Infinity);
+    }
+
+    #[test]
+    fn test_add_degenerate_inputs() {
+        let curve = EcGroup::cryptopals().curve;
+        let p = &curve.p;
+
+        // Doubling a point with y = 0, or adding P to -P written unreduced
+        let x = BigUint::from(5u32);
+        assert_eq!(curve.double(&Point::new(x.clone(), BigUint::zero())), Point::Infinity);
+        assert_eq!(curve.add(&Point::new(x.clone(), BigUint::one()), &Point::new(x.clone() + p, p - 1u32)), Point::Infinity);
+
+        // Off-curve points sharing an x have no chord between them
+        assert_eq!(curve.add(&Point::new(x.clone(), BigUint::one()), &Point::new(x, BigUint::from(2u32))), Point::Infinity);
+    }
+
+    #[test]
+    fn test_kangaroo() {
+        let group = EcGroup::cryptopals();
+        let lower = BigUint::from(1u64 << 40);
+        let x = &lower + 123_456u32;
+        let y = group.multiply(&x);
+
+        assert_eq!(kangaroo(&group, &y, &lower, &(&lower + (1u32 << 20))), Some(x));
+    }
+}
@@ -0,0 +1,228 @@
+// crypto/src/ec/montgomery.rs
+use crate::bigint::{is_quadratic_residue, mod_sub, modinv, random_below, sqrt_mod, BigUint, One, Zero};
+
+use super::{EcGroup, Point, WeierstrassCurve};
+
+/// Bv^2 = u^3 + Au^2 + u over GF(p)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MontgomeryCurve {
+    pub p: BigUint,
+    pub a: BigUint,
+    pub b: BigUint,
+}
+
+impl MontgomeryCurve {
+    pub fn new(p: BigUint, a: BigUint, b: BigUint) -> Self {
+        Self { p, a, b }
+    }
+
+    /// u^3 + Au^2 + u, which must be B times a square for u to be on the curve
+    fn rhs(&self, u: &BigUint) -> BigUint {
+        (u * u * u + &self.a * u * u + u) % &self.p
+    }
+
+    fn v_squared(&self, u: &BigUint) -> BigUint {
+        self.rhs(u) * modinv(&self.b, &self.p).unwrap() % &self.p
+    }
+
+    pub fn contains_u(&self, u: &BigUint) -> bool {
+        let v_squared = self.v_squared(u);
+        v_squared.is_zero() || is_quadratic_residue(&v_squared, &self.p)
+    }
+
+    /// u-coordinates that aren't on the curve are on its quadratic twist, and
+    /// an x-only ladder computes on the twist without noticing
+    pub fn on_twist(&self, u: &BigUint) -> bool {
+        !self.contains_u(u)
+    }
+
+    pub fn random_twist_u(&self) -> BigUint {
+        loop {
+            let u = random_below(&self.p);
+            if self.on_twist(&u) {
+                return u;
+            }
+        }
+    }
+
+    /// The RFC 7748 ladder on projective (X : Z). Returns the u-coordinate of
+    /// k * (u, v), with 0 standing in for the point at infinity. B never enters
+    /// the formulas, which is why twist points go through unchallenged.
+    pub fn ladder(&self, u: &BigUint, k: &BigUint) -> BigUint {
+        let p = &self.p;
+        let a24 = mod_sub(&self.a, &BigUint::from(2u32), p) * modinv(&BigUint::from(4u32), p).unwrap() % p;
+        let x1 = u % p;
+
+        let (mut x2, mut z2) = (BigUint::one(), BigUint::zero());
+        let (mut x3, mut z3) = (x1.clone(), BigUint::one());
+
+        for i in (0..k.bits()).rev() {
+            if k.bit(i) {
+                std::mem::swap(&mut x2, &mut x3);
+                std::mem::swap(&mut z2, &mut z3);
+            }
+
+            let a = (&x2 + &z2) % p;
+            let aa = &a * &a % p;
+            let b = mod_sub(&x2, &z2, p);
+            let bb = &b * &b % p;
+            let e = mod_sub(&aa, &bb, p);
+            let c = (&x3 + &z3) % p;
+            let d = mod_sub(&x3, &z3, p);
+            let da = d * &a % p;
+            let cb = c * &b % p;
+
+            let sum = (&da + &cb) % p;
+            let difference = mod_sub(&da, &cb, p);
+            x3 = &sum * &sum % p;
+            z3 = &x1 * (&difference * &difference % p) % p;
+            x2 = &aa * &bb % p;
+            z2 = &e * ((&aa + &a24 * &e) % p) % p;
+
+            if k.bit(i) {
+                std::mem::swap(&mut x2, &mut x3);
+                std::mem::swap(&mut z2, &mut z3);
+            }
+        }
+
+        match modinv(&z2, p) {
+            Some(inverse) => x2 * inverse % p,
+            None => BigUint::zero(),
+        }
+    }
+
+    /// u(P), u(2P), u(3P), ... by differential addition, one step per multiple.
+    /// Yields 0 for the point at infinity and stops after it.
+    pub fn multiples(&self, u: &BigUint) -> impl Iterator<Item = BigUint> + '_ {
+        let p = self.p.clone();
+        let base = u % &p;
+        let mut previous: Option<(BigUint, BigUint)> = None;
+        let mut current: Option<(BigUint, BigUint)> = None;
+        let mut done = false;
+
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+
+            let next = match (&previous, &current) {
+                (_, None) => (base.clone(), BigUint::one()),
+                (None, Some(_)) => (self.ladder(&base, &BigUint::from(2u32)), BigUint::one()),
+                (Some((xd, zd)), Some((xm, zm))) => {
+                    let t1 = mod_sub(xm, zm, &p) * ((&base + 1u32) % &p) % &p;
+                    let t2 = (xm + zm) * mod_sub(&base, &BigUint::one(), &p) % &p;
+                    let sum = (&t1 + &t2) % &p;
+                    let difference = mod_sub(&t1, &t2, &p);
+                    (zd * (&sum * &sum % &p) % &p, xd * (&difference * &difference % &p) % &p)
+                }
+            };
+
+            let affine = modinv(&next.1, &p).map(|inverse| &next.0 * inverse % &p);
+            done = affine.is_none();
+            previous = current.take();
+            current = Some(next);
+            Some(affine.unwrap_or_default())
+        })
+    }
+
+    /// The birationally equivalent short-Weierstrass curve:
+    /// x = (u + A/3) / B, y = v / B
+    pub fn to_weierstrass(&self) -> WeierstrassCurve {
+        let p = &self.p;
+        let three_inverse = modinv(&BigUint::from(3u32), p).unwrap();
+        let b_inverse = modinv(&self.b, p).unwrap();
+        let a_squared = &self.a * &self.a % p;
+
+        let a = mod_sub(&BigUint::from(3u32), &a_squared, p) * &three_inverse % p * &b_inverse % p * &b_inverse % p;
+        let b = mod_sub(&(BigUint::from(2u32) * &a_squared * &self.a), &(BigUint::from(9u32) * &self.a), p)
+            * modinv(&BigUint::from(27u32), p).unwrap() % p
+            * b_inverse.modpow(&BigUint::from(3u32), p) % p;
+
+        WeierstrassCurve::new(p.clone(), a, b)
+    }
+
+    pub fn u_to_x(&self, u: &BigUint) -> BigUint {
+        let p = &self.p;
+        let a_third = &self.a * modinv(&BigUint::from(3u32), p).unwrap() % p;
+        (u + a_third) % p * modinv(&self.b, p).unwrap() % p
+    }
+
+    /// One of the two Weierstrass points over u, if u is on the curve
+    pub fn lift_u(&self, u: &BigUint) -> Option<Point> {
+        let v = sqrt_mod(&self.v_squared(u), &self.p)?;
+        let y = v * modinv(&self.b, &self.p).unwrap() % &self.p;
+        Some(Point::new(self.u_to_x(u), y))
+    }
+}
+
+/// A Montgomery curve with a base u-coordinate of prime order n and cofactor h
+#[derive(Debug, Clone, PartialEq)]
+pub struct MontgomeryGroup {
+    pub curve: MontgomeryCurve,
+    pub u: BigUint,
+    pub n: BigUint,
+    pub cofactor: BigUint,
+}
+
+impl MontgomeryGroup {
+    pub fn new(curve: MontgomeryCurve, u: BigUint, n: BigUint, cofactor: BigUint) -> Self {
+        Self { curve, u, n, cofactor }
+    }
+
+    /// v^2 = u^3 + 534u^2 + u from cryptopals 60, the Montgomery form of
+    /// `EcGroup::cryptopals`
+    pub fn cryptopals() -> Self {
+        let p = "233970423115425145524320034830162017933".parse().unwrap();
+        let curve = MontgomeryCurve::new(p, BigUint::from(534u32), BigUint::one());
+        let n = "29246302889428143187362802287225875743".parse().unwrap();
+
+        Self { curve, u: BigUint::from(4u32), n, cofactor: BigUint::from(8u32) }
+    }
+
+    pub fn order(&self) -> BigUint {
+        &self.n * &self.cofactor
+    }
+
+    /// #E + #E' = 2p + 2
+    pub fn twist_order(&self) -> BigUint {
+        BigUint::from(2u32) * &self.curve.p + 2u32 - self.order()
+    }
+
+    /// The same group with full (x, y) arithmetic, for the parts of an attack
+    /// that need real point addition
+    pub fn to_weierstrass(&self) -> EcGroup {
+        let g = self.curve.lift_u(&self.u).expect("base point is on the curve");
+        EcGroup::new(self.curve.to_weierstrass(), g, self.n.clone())
+    }
+
+    pub fn multiply(&self, k: &BigUint) -> BigUint {
+        self.curve.ladder(&self.u, k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ladder_matches_weierstrass() {
+        let montgomery = MontgomeryGroup::cryptopals();
+        let weierstrass = montgomery.to_weierstrass();
+        assert_eq!(weierstrass.curve, EcGroup::cryptopals().curve);
+        assert_eq!(weierstrass.g.x(), EcGroup::cryptopals().g.x());
+
+        let k = weierstrass.random_scalar();
+        let expected = weierstrass.multiply(&k);
+        assert_eq!(montgomery.curve.u_to_x(&montgomery.multiply(&k)), *expected.x().unwrap());
+        assert_eq!(montgomery.multiply(&montgomery.n), BigUint::zero());
+    }
+
+    #[test]
+    fn test_multiples() {
+        let group = MontgomeryGroup::cryptopals();
+        let u = group.curve.random_twist_u();
+        for (k, multiple) in group.curve.multiples(&u).take(20).enumerate() {
+            assert_eq!(multiple, group.curve.ladder(&u, &BigUint::from(k + 1)));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// crypto/src/gf2_128/mod.rs
+pub mod poly;
+
+pub use poly::Polynomial;
+
+use std::fmt;
+use std::ops::{Add, Mul};
+
+use crate::utils::oracle::random_bytes;
+
+/// x^128 + x^7 + x^2 + x + 1 in GCM's reflected bit order
+const R: u128 = 0xe1 << 120;
+
+/// An element of GF(2^128) in GCM's convention: the most significant bit of the
+/// big-endian block is the coefficient of x^0
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Gf128(pub u128);
+
+impl Gf128 {
+    pub const ZERO: Gf128 = Gf128(0);
+    pub const ONE: Gf128 = Gf128(1 << 127);
+
+    pub fn from_block(block: &[u8]) -> Self {
+        let mut bytes = [0u8; 16];
+        bytes[..block.len()].copy_from_slice(block);
+        Gf128(u128::from_be_bytes(bytes))
+    }
+
+    pub fn to_block(self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    pub fn random() -> Self {
+        Self::from_block(&random_bytes(16))
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn square(self) -> Self {
+        self * self
+    }
+
+    pub fn pow(self, mut exponent: u128) -> Self {
+        let (mut base, mut result) = (self, Self::ONE);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base;
+            }
+            base = base.square();
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// a^(2^128 - 2); None for zero
+    pub fn inverse(self) -> Option<Self> {
+        (!self.is_zero()).then(|| self.pow(u128::MAX - 1))
+    }
+
+    /// Squaring is a bijection in characteristic 2, so every element has a
+    /// unique root: a^(2^127)
+    pub fn sqrt(self) -> Self {
+        (0..127).fold(self, |a, _| a.square())
+    }
+}
+
+/// Addition in characteristic 2 is XOR
+impl Add for Gf128 {
+    type Output = Gf128;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Gf128) -> Gf128 {
+        Gf128(self.0 ^ other.0)
+    }
+}
+
+/// Algorithm 1 of NIST SP 800-38D, shift-and-add with reduction by R
+impl Mul for Gf128 {
+    type Output = Gf128;
+
+    fn mul(self, other: Gf128) -> Gf128 {
+        let (mut z, mut v) = (0u128, other.0);
+        for i in (0..128).rev() {
+            if self.0 >> i & 1 == 1 {
+                z ^= v;
+            }
+            v = if v & 1 == 1 { (v >> 1) ^ R } else { v >> 1 };
+        }
+        Gf128(z)
+    }
+}
+
+impl fmt::Debug for Gf128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Gf128({:032x})", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_arithmetic() {
+        let a = Gf128::random();
+        let b = Gf128::random();
+
+        assert_eq!(a * Gf128::ONE, a);
+        assert_eq!(a * b, b * a);
+        assert_eq!(a * a.inverse().unwrap(), Gf128::ONE);
+        assert_eq!(a.sqrt().square(), a);
+        assert_eq!((a + b) * b, a * b + b * b);
+        assert!(Gf128::ZERO.inverse().is_none());
+    }
+}
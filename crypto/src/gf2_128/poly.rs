@@ -0,0 +1,319 @@
+// crypto/src/gf2_128/poly.rs
+use std::ops::{Add, Mul};
+
+use super::Gf128;
+
+/// A polynomial over GF(2^128), coefficients stored lowest degree first with
+/// no trailing zeros
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coeffs: Vec<Gf128>,
+}
+
+impl Polynomial {
+    pub fn new(mut coeffs: Vec<Gf128>) -> Self {
+        while coeffs.last().is_some_and(|c| c.is_zero()) {
+            coeffs.pop();
+        }
+        Self { coeffs }
+    }
+
+    pub fn zero() -> Self {
+        Self { coeffs: Vec::new() }
+    }
+
+    pub fn one() -> Self {
+        Self::constant(Gf128::ONE)
+    }
+
+    pub fn x() -> Self {
+        Self::new(vec![Gf128::ZERO, Gf128::ONE])
+    }
+
+    pub fn constant(c: Gf128) -> Self {
+        Self::new(vec![c])
+    }
+
+    /// A random polynomial of degree below `bound`
+    pub fn random(bound: usize) -> Self {
+        Self::new((0..bound).map(|_| Gf128::random()).collect())
+    }
+
+    pub fn coefficients(&self) -> &[Gf128] {
+        &self.coeffs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.coeffs == [Gf128::ONE]
+    }
+
+    /// None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn leading(&self) -> Gf128 {
+        self.coeffs.last().copied().unwrap_or(Gf128::ZERO)
+    }
+
+    pub fn monic(&self) -> Self {
+        match self.leading().inverse() {
+            Some(inverse) => self.scale(inverse),
+            None => Self::zero(),
+        }
+    }
+
+    pub fn scale(&self, c: Gf128) -> Self {
+        Self::new(self.coeffs.iter().map(|&a| a * c).collect())
+    }
+
+    pub fn eval(&self, x: Gf128) -> Gf128 {
+        self.coeffs.iter().rev().fold(Gf128::ZERO, |acc, &c| acc * x + c)
+    }
+
+    /// Long division; panics on a zero divisor
+    pub fn div_rem(&self, divisor: &Polynomial) -> (Polynomial, Polynomial) {
+        let divisor_degree = divisor.degree().expect("division by the zero polynomial");
+        let inverse = divisor.leading().inverse().unwrap();
+
+        let mut remainder = self.coeffs.clone();
+        let mut quotient = vec![Gf128::ZERO; self.coeffs.len().saturating_sub(divisor_degree)];
+
+        while remainder.len() > divisor_degree {
+            let shift = remainder.len() - 1 - divisor_degree;
+            let factor = *remainder.last().unwrap() * inverse;
+            quotient[shift] = factor;
+
+            for (i, &c) in divisor.coeffs.iter().enumerate() {
+                remainder[shift + i] = remainder[shift + i] + c * factor;
+            }
+            remainder.pop();
+        }
+
+        (Self::new(quotient), Self::new(remainder))
+    }
+
+    pub fn rem(&self, modulus: &Polynomial) -> Polynomial {
+        self.div_rem(modulus).1
+    }
+
+    /// Monic greatest common divisor
+    pub fn gcd(&self, other: &Polynomial) -> Polynomial {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = a.rem(&b);
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// In characteristic 2 only the odd-degree terms survive
+    pub fn derivative(&self) -> Polynomial {
+        Self::new(self.coeffs.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| if i % 2 == 1 { c } else { Gf128::ZERO })
+            .collect())
+    }
+
+    /// The square root of a polynomial whose derivative is zero (only even powers)
+    pub fn sqrt(&self) -> Polynomial {
+        Self::new(self.coeffs.iter().step_by(2).map(|c| c.sqrt()).collect())
+    }
+
+    /// self^(2^k) mod modulus, by repeated squaring
+    pub fn pow2k_mod(&self, k: usize, modulus: &Polynomial) -> Polynomial {
+        (0..k).fold(self.rem(modulus), |p, _| (&p * &p).rem(modulus))
+    }
+
+    /// Splits a monic polynomial into square-free factors with their
+    /// multiplicities (Yun's algorithm with the char-2 square-root step)
+    pub fn square_free_factorization(&self) -> Vec<(Polynomial, usize)> {
+        let f = self.monic();
+        let mut factors = Vec::new();
+
+        let mut c = f.gcd(&f.derivative());
+        let mut w = f.div_rem(&c).0;
+        let mut i = 1;
+
+        while !w.is_one() {
+            let y = w.gcd(&c);
+            let factor = w.div_rem(&y).0;
+            if !factor.is_one() {
+                factors.push((factor, i));
+            }
+            c = c.div_rem(&y).0;
+            w = y;
+            i += 1;
+        }
+
+        if !c.is_one() {
+            for (factor, multiplicity) in c.sqrt().square_free_factorization() {
+                factors.push((factor, multiplicity * 2));
+            }
+        }
+
+        factors
+    }
+
+    /// For a monic square-free polynomial, groups its irreducible factors by
+    /// degree: (product of all degree-d factors, d). Uses gcd(f, x^(q^d) - x)
+    /// with q = 2^128.
+    pub fn distinct_degree_factorization(&self) -> Vec<(Polynomial, usize)> {
+        let mut f = self.monic();
+        let mut factors = Vec::new();
+        let mut h = Polynomial::x();
+        let mut d = 1;
+
+        while f.degree().unwrap_or(0) >= 2 * d {
+            h = h.pow2k_mod(128, &f);
+            let g = f.gcd(&(&h + &Polynomial::x()));
+            if !g.is_one() {
+                f = f.div_rem(&g).0;
+                h = h.rem(&f);
+                factors.push((g, d));
+            }
+            d += 1;
+        }
+
+        if let Some(degree) = f.degree()
+            && degree > 0
+        {
+            factors.push((f, degree));
+        }
+
+        factors
+    }
+
+    /// Cantor–Zassenhaus for a product of distinct degree-d irreducibles. In
+    /// characteristic 2 the splitting map is the trace a + a^2 + ... + a^(2^(128d - 1)),
+    /// which is 0 or 1 mod each factor, so gcd(f, trace) splits f about half the time.
+    pub fn equal_degree_factorization(&self, d: usize) -> Vec<Polynomial> {
+        let f = self.monic();
+        let n = f.degree().unwrap_or(0);
+        if n == 0 || d == 0 {
+            return Vec::new();
+        }
+
+        let mut factors = vec![f.clone()];
+        while factors.len() < n / d {
+            let a = Polynomial::random(n);
+            let mut term = a.rem(&f);
+            let mut trace = term.clone();
+            for _ in 1..128 * d {
+                term = (&term * &term).rem(&f);
+                trace = &trace + &term;
+            }
+
+            factors = factors.into_iter()
+                .flat_map(|u| {
+                    if u.degree() == Some(d) {
+                        return vec![u];
+                    }
+                    let g = u.gcd(&trace);
+                    if g.is_one() || g == u {
+                        vec![u]
+                    } else {
+                        let other = u.div_rem(&g).0;
+                        vec![g, other]
+                    }
+                })
+                .collect();
+        }
+
+        factors
+    }
+
+    /// Every distinct root in GF(2^128)
+    pub fn roots(&self) -> Vec<Gf128> {
+        self.square_free_factorization()
+            .into_iter()
+            .flat_map(|(factor, _)| factor.distinct_degree_factorization())
+            .filter(|(_, degree)| *degree == 1)
+            .flat_map(|(product, _)| product.equal_degree_factorization(1))
+            .map(|linear| linear.coeffs[0])
+            .collect()
+    }
+}
+
+impl Add for &Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &Polynomial) -> Polynomial {
+        let (long, short) = if self.coeffs.len() >= other.coeffs.len() { (self, other) } else { (other, self) };
+        let mut coeffs = long.coeffs.clone();
+        for (c, &s) in coeffs.iter_mut().zip(&short.coeffs) {
+            *c = *c + s;
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl Mul for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: &Polynomial) -> Polynomial {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::zero();
+        }
+
+        let mut coeffs = vec![Gf128::ZERO; self.coeffs.len() + other.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in other.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j] + a * b;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn linear(root: Gf128) -> Polynomial {
+        Polynomial::new(vec![root, Gf128::ONE])
+    }
+
+    #[test]
+    fn test_division_and_gcd() {
+        let a = Polynomial::random(6);
+        let b = Polynomial::random(4);
+        let (q, r) = a.div_rem(&b);
+        assert_eq!(&(&q * &b) + &r, a);
+        assert!(r.degree() < b.degree());
+
+        let common = linear(Gf128::random());
+        assert_eq!((&a * &common).gcd(&(&b * &common)).degree(), Some(1));
+    }
+
+    #[test]
+    fn test_factorization() {
+        let roots: Vec<Gf128> = (0..3).map(|_| Gf128::random()).collect();
+        let quadratic_with_no_roots = loop {
+            let q = Polynomial::new(vec![Gf128::random(), Gf128::random(), Gf128::ONE]);
+            if q.distinct_degree_factorization() == vec![(q.clone(), 2)] {
+                break q;
+            }
+        };
+
+        // (x - r0)^2 (x - r1) (x - r2) * irreducible quadratic
+        let f = [linear(roots[0]), linear(roots[0]), linear(roots[1]), linear(roots[2]), quadratic_with_no_roots.clone()]
+            .iter()
+            .fold(Polynomial::one(), |acc, p| &acc * p);
+
+        let square_free = f.square_free_factorization();
+        assert!(square_free.contains(&(linear(roots[0]), 2)));
+
+        let mut found = f.roots();
+        found.sort_by_key(|r| r.0);
+        let mut expected = roots.clone();
+        expected.sort_by_key(|r| r.0);
+        assert_eq!(found, expected);
+    }
+}
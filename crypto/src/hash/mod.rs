@@ -0,0 +1,83 @@
+// crypto/src/hash/mod.rs
+pub mod multicollision;
+pub mod sha1;
+pub mod sha256;
+pub mod sha512;
+pub mod weak;
+
+pub use sha1::{sha1, Sha1};
+pub use sha256::{sha224, sha256, Sha224, Sha256};
+pub use sha512::{sha384, sha512, Sha384, Sha512};
+pub use weak::WeakHash;
+
+/// Digest choice for the signature schemes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DigestAlgorithm {
+    Sha1,
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    pub fn hash(&self, message: &[u8]) -> Vec<u8> {
+        match self {
+            DigestAlgorithm::Sha1 => sha1(message),
+            DigestAlgorithm::Sha256 => sha256(message),
+        }
+    }
+}
+
+/// Streaming interface shared by the Merkle-Damgård hashes
+pub trait Hash: Clone {
+    const BLOCK_SIZE: usize;
+    const OUTPUT_SIZE: usize;
+
+    fn new() -> Self;
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self) -> Vec<u8>;
+
+    fn digest(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+/// Merkle-Damgård strengthening: a 1 bit, zeros, then the big-endian bit
+/// length in a `length_bytes` wide field, filling out the final block
+pub(crate) fn md_padding(length: u64, block_size: usize, length_bytes: usize) -> Vec<u8> {
+    let bit_length = (length as u128).wrapping_mul(8);
+    let used = (length as usize + 1 + length_bytes) % block_size;
+    let zeros = (block_size - used) % block_size;
+
+    let mut padding = Vec::with_capacity(1 + zeros + length_bytes);
+    padding.push(0x80);
+    padding.resize(1 + zeros, 0);
+    padding.extend_from_slice(&bit_length.to_be_bytes()[16 - length_bytes..]);
+
+    padding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha2_resume_from_state() {
+        let message = b"comment1=cooking%20MCs;userdata=foo";
+        let suffix = b";admin=true";
+
+        let mut glued = message.to_vec();
+        glued.extend_from_slice(&Sha256::padding(message.len() as u64));
+        let mut hasher = Sha256::from_state(Sha256::state_from_digest(&sha256(message)).unwrap(), glued.len() as u64);
+        hasher.update(suffix);
+        glued.extend_from_slice(suffix);
+        assert_eq!(hasher.finalize(), sha256(&glued));
+
+        let mut glued = message.to_vec();
+        glued.extend_from_slice(&Sha512::padding(message.len() as u64));
+        let mut hasher = Sha512::from_state(Sha512::state_from_digest(&sha512(message)).unwrap(), glued.len() as u64);
+        hasher.update(suffix);
+        glued.extend_from_slice(suffix);
+        assert_eq!(hasher.finalize(), sha512(&glued));
+    }
+}
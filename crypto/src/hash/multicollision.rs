@@ -0,0 +1,335 @@
+// crypto/src/hash/multicollision.rs
+use std::collections::HashMap;
+
+use crate::utils::oracle::random_bytes;
+
+use super::weak::{WeakHash, WEAK_BLOCK_SIZE};
+
+/// A fresh source of distinct blocks: a random 8-byte tag followed by a counter
+struct BlockSource {
+    tag: Vec<u8>,
+    counter: u64,
+}
+
+impl BlockSource {
+    fn new() -> Self {
+        Self { tag: random_bytes(8), counter: 0 }
+    }
+
+    fn next_block(&mut self) -> Vec<u8> {
+        self.counter += 1;
+        [&self.tag[..], &self.counter.to_be_bytes()].concat()
+    }
+}
+
+/// Birthday search for blocks b1, b2 with compress(a, b1) == compress(b, b2),
+/// returning both blocks and the shared state. Works for a == b too, where it
+/// gives an ordinary single-block collision.
+pub fn find_collision(hash: &WeakHash, a: u32, b: u32) -> (Vec<u8>, Vec<u8>, u32) {
+    let mut from_a: HashMap<u32, Vec<u8>> = HashMap::new();
+    let mut from_b: HashMap<u32, Vec<u8>> = HashMap::new();
+    let (mut source_a, mut source_b) = (BlockSource::new(), BlockSource::new());
+
+    loop {
+        let block_a = source_a.next_block();
+        let state_a = hash.compress(a, &block_a);
+        if let Some(block_b) = from_b.get(&state_a) {
+            return (block_a, block_b.clone(), state_a);
+        }
+
+        let block_b = source_b.next_block();
+        let state_b = hash.compress(b, &block_b);
+        if let Some(block_a) = from_a.get(&state_b) {
+            return (block_a.clone(), block_b, state_b);
+        }
+
+        from_a.insert(state_a, block_a);
+        from_b.insert(state_b, block_b);
+    }
+}
+
+/// Joux's construction: n successive single-block collisions, any path through
+/// which gives the same final state, for 2^n colliding messages at the cost of n
+#[derive(Debug, Clone)]
+pub struct Multicollision {
+    pub blocks: Vec<(Vec<u8>, Vec<u8>)>,
+    pub state: u32,
+}
+
+impl Multicollision {
+    pub fn new(hash: &WeakHash, state: u32, n: usize) -> Self {
+        let mut multicollision = Self { blocks: Vec::new(), state };
+        multicollision.extend(hash, n);
+        multicollision
+    }
+
+    /// Adds n more stages, doubling the number of messages each time
+    pub fn extend(&mut self, hash: &WeakHash, n: usize) {
+        for _ in 0..n {
+            let (first, second, state) = find_collision(hash, self.state, self.state);
+            self.blocks.push((first, second));
+            self.state = state;
+        }
+    }
+
+    /// Every colliding message, each `blocks.len()` blocks long
+    pub fn messages(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        (0u64..1 << self.blocks.len()).map(|choice| {
+            self.blocks.iter()
+                .enumerate()
+                .flat_map(|(i, (first, second))| if choice >> i & 1 == 0 { first } else { second })
+                .copied()
+                .collect()
+        })
+    }
+}
+
+/// Collides `cheap(m) || expensive(m)`: keep growing a Joux multicollision in the
+/// cheap hash until two of its 2^n messages also collide under the expensive one,
+/// which takes about 2^(expensive bits / 2) messages
+pub fn cascade_collision(cheap: &WeakHash, expensive: &WeakHash) -> (Vec<u8>, Vec<u8>) {
+    let mut multicollision = Multicollision::new(cheap, cheap.initial_state(), expensive.bits() as usize / 2);
+
+    loop {
+        let mut seen: HashMap<u32, Vec<u8>> = HashMap::new();
+        for message in multicollision.messages() {
+            if let Some(other) = seen.insert(expensive.hash(&message), message.clone()) {
+                return (other, message);
+            }
+        }
+        multicollision.extend(cheap, 1);
+    }
+}
+
+/// Kelsey–Schneier expandable message: k pieces, each a choice between one block
+/// and 2^i + 1 blocks that reach the same state, so any length from k to
+/// k + 2^k - 1 blocks can be produced with a fixed final state
+#[derive(Debug, Clone)]
+pub struct ExpandableMessage {
+    pub pieces: Vec<(Vec<u8>, Vec<u8>)>,
+    pub state: u32,
+}
+
+impl ExpandableMessage {
+    pub fn new(hash: &WeakHash, k: usize) -> Self {
+        let mut state = hash.initial_state();
+        let mut pieces = Vec::with_capacity(k);
+
+        for i in (0..k).rev() {
+            let dummy = vec![0u8; WEAK_BLOCK_SIZE << i];
+            let dummy_state = hash.chain(state, &dummy);
+
+            let (short, last, next) = find_collision(hash, state, dummy_state);
+            pieces.push((short, [dummy, last].concat()));
+            state = next;
+        }
+
+        Self { pieces, state }
+    }
+
+    pub fn min_blocks(&self) -> usize {
+        self.pieces.len()
+    }
+
+    pub fn max_blocks(&self) -> usize {
+        self.pieces.len() + (1 << self.pieces.len()) - 1
+    }
+
+    /// A prefix of exactly `blocks` blocks that ends in `self.state`
+    pub fn produce(&self, blocks: usize) -> Option<Vec<u8>> {
+        if blocks < self.min_blocks() || blocks > self.max_blocks() {
+            return None;
+        }
+
+        let extra = blocks - self.min_blocks();
+        let mut message = Vec::with_capacity(blocks * WEAK_BLOCK_SIZE);
+        for (short, long) in &self.pieces {
+            let long_extra = long.len() / WEAK_BLOCK_SIZE - 1;
+            message.extend_from_slice(if extra & long_extra != 0 { long } else { short });
+        }
+
+        Some(message)
+    }
+}
+
+/// Second preimage for a long message: build an expandable message, find one
+/// bridge block from its state into any intermediate state of the target, then
+/// stretch the prefix so the bridge lands at the right position. The forgery has
+/// the same length, so the padding block matches too. Costs about 2^bits / 2^k
+/// compressions for a 2^k-block target.
+pub fn second_preimage(hash: &WeakHash, message: &[u8]) -> Option<Vec<u8>> {
+    let blocks = message.len() / WEAK_BLOCK_SIZE;
+    if blocks < 4 {
+        return None;
+    }
+    let k = (blocks - 1).ilog2() as usize;
+    let expandable = ExpandableMessage::new(hash, k);
+
+    // The state after block i is a usable target if the prefix can be i blocks long
+    let mut targets = HashMap::new();
+    let mut state = hash.initial_state();
+    for (i, block) in message[..blocks * WEAK_BLOCK_SIZE].chunks_exact(WEAK_BLOCK_SIZE).enumerate() {
+        state = hash.compress(state, block);
+        if (expandable.min_blocks()..=expandable.max_blocks()).contains(&i) {
+            targets.entry(state).or_insert(i + 1);
+        }
+    }
+
+    let mut source = BlockSource::new();
+    loop {
+        let bridge = source.next_block();
+        if let Some(&position) = targets.get(&hash.compress(expandable.state, &bridge)) {
+            let prefix = expandable.produce(position - 1)?;
+            return Some([&prefix, &bridge[..], &message[position * WEAK_BLOCK_SIZE..]].concat());
+        }
+    }
+}
+
+/// Kelsey–Kohno diamond structure: 2^k leaf states funnelled pairwise through k
+/// levels of collisions into a single root
+#[derive(Debug, Clone)]
+pub struct DiamondStructure {
+    /// states[0] are the leaves, states[k] is just the root
+    pub states: Vec<Vec<u32>>,
+    /// links[level][node] takes states[level][node] to its parent
+    pub links: Vec<Vec<Vec<u8>>>,
+}
+
+impl DiamondStructure {
+    pub fn new(hash: &WeakHash, k: usize) -> Self {
+        let mask = u32::MAX >> (32 - hash.bits());
+        let mut leaves: Vec<u32> = Vec::with_capacity(1 << k);
+        while leaves.len() < 1 << k {
+            let bytes = random_bytes(4);
+            let leaf = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) & mask;
+            if !leaves.contains(&leaf) {
+                leaves.push(leaf);
+            }
+        }
+
+        let mut states = vec![leaves];
+        let mut links = Vec::with_capacity(k);
+        while states.last().unwrap().len() > 1 {
+            let (mut parents, mut level_links) = (Vec::new(), Vec::new());
+            for pair in states.last().unwrap().chunks_exact(2) {
+                let (left, right, parent) = find_collision(hash, pair[0], pair[1]);
+                level_links.extend([left, right]);
+                parents.push(parent);
+            }
+            states.push(parents);
+            links.push(level_links);
+        }
+
+        Self { states, links }
+    }
+
+    pub fn root(&self) -> u32 {
+        self.states.last().unwrap()[0]
+    }
+
+    pub fn depth(&self) -> usize {
+        self.links.len()
+    }
+
+    /// The blocks taking a leaf to the root
+    pub fn path(&self, leaf: usize) -> Vec<u8> {
+        (0..self.depth()).flat_map(|level| self.links[level][leaf >> level].clone()).collect()
+    }
+}
+
+/// A herding commitment: a digest published up front that can later be opened
+/// to a message starting with any prefix of up to `prefix_blocks` blocks
+#[derive(Debug, Clone)]
+pub struct Prediction {
+    pub digest: u32,
+    pub prefix_blocks: usize,
+    diamond: DiamondStructure,
+}
+
+impl Prediction {
+    pub fn new(hash: &WeakHash, k: usize, prefix_blocks: usize) -> Self {
+        let diamond = DiamondStructure::new(hash, k);
+        let length = (prefix_blocks + 1 + k) * WEAK_BLOCK_SIZE;
+        let digest = hash.finish(diamond.root(), length as u64);
+
+        Self { digest, prefix_blocks, diamond }
+    }
+
+    /// Pads the prefix with spaces to its committed length, then searches for a
+    /// link block into any leaf of the diamond and follows its path to the root
+    pub fn forge(&self, hash: &WeakHash, prefix: &[u8]) -> Option<Vec<u8>> {
+        let mut message = prefix.to_vec();
+        if message.len() > self.prefix_blocks * WEAK_BLOCK_SIZE {
+            return None;
+        }
+        message.resize(self.prefix_blocks * WEAK_BLOCK_SIZE, b' ');
+
+        let leaves: HashMap<u32, usize> = self.diamond.states[0].iter().enumerate().map(|(i, &s)| (s, i)).collect();
+        let state = hash.chain(hash.initial_state(), &message);
+
+        let mut source = BlockSource::new();
+        loop {
+            let link = source.next_block();
+            if let Some(&leaf) = leaves.get(&hash.compress(state, &link)) {
+                message.extend_from_slice(&link);
+                message.extend_from_slice(&self.diamond.path(leaf));
+                return Some(message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_joux_multicollision() {
+        let hash = WeakHash::new(16);
+        let multicollision = Multicollision::new(&hash, hash.initial_state(), 4);
+
+        let messages: HashSet<Vec<u8>> = multicollision.messages().collect();
+        assert_eq!(messages.len(), 16);
+        let digests: HashSet<u32> = messages.iter().map(|m| hash.hash(m)).collect();
+        assert_eq!(digests.len(), 1);
+    }
+
+    #[test]
+    fn test_cascade_collision() {
+        let (cheap, expensive) = (WeakHash::new(16), WeakHash::new(24));
+        let (a, b) = cascade_collision(&cheap, &expensive);
+
+        assert_ne!(a, b);
+        assert_eq!(cheap.hash(&a), cheap.hash(&b));
+        assert_eq!(expensive.hash(&a), expensive.hash(&b));
+    }
+
+    #[test]
+    fn test_expandable_second_preimage() {
+        let hash = WeakHash::new(24);
+        let message: Vec<u8> = (0..(1 << 10) * WEAK_BLOCK_SIZE).map(|i| (i * 31 % 251) as u8).collect();
+
+        let expandable = ExpandableMessage::new(&hash, 4);
+        for blocks in expandable.min_blocks()..=expandable.max_blocks() {
+            let prefix = expandable.produce(blocks).unwrap();
+            assert_eq!(prefix.len(), blocks * WEAK_BLOCK_SIZE);
+            assert_eq!(hash.chain(hash.initial_state(), &prefix), expandable.state);
+        }
+
+        let forged = second_preimage(&hash, &message).unwrap();
+        assert_ne!(forged, message);
+        assert_eq!(forged.len(), message.len());
+        assert_eq!(hash.hash(&forged), hash.hash(&message));
+    }
+
+    #[test]
+    fn test_herding() {
+        let hash = WeakHash::new(16);
+        let prediction = Prediction::new(&hash, 6, 4);
+
+        let forged = prediction.forge(&hash, b"Final score: Rustaceans 3, Gophers 1").unwrap();
+        assert!(forged.starts_with(b"Final score: Rustaceans 3, Gophers 1"));
+        assert_eq!(hash.hash(&forged), prediction.digest);
+    }
+}
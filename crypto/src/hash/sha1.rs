@@ -0,0 +1,179 @@
+// crypto/src/hash/sha1.rs
+use super::{md_padding, Hash};
+
+const SHA1_BLOCK_SIZE: usize = 64;
+const SHA1_OUTPUT_SIZE: usize = 20;
+
+const INITIAL_STATE: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+#[derive(Debug, Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    buffer: Vec<u8>,
+    length: u64,
+}
+
+impl Sha1 {
+    pub fn new() -> Self {
+        Self::from_state(INITIAL_STATE, 0)
+    }
+
+    /// Resume hashing from a known internal state, e.g. a published digest.
+    /// `length` is the number of bytes already absorbed (including padding),
+    /// which is what the final length field will be computed from.
+    pub fn from_state(state: [u32; 5], length: u64) -> Self {
+        Self {
+            state,
+            buffer: Vec::with_capacity(SHA1_BLOCK_SIZE),
+            length,
+        }
+    }
+
+    /// Split a digest back into the five chaining words
+    pub fn state_from_digest(digest: &[u8]) -> Option<[u32; 5]> {
+        if digest.len() != SHA1_OUTPUT_SIZE {
+            return None;
+        }
+
+        let mut state = [0u32; 5];
+        for (word, chunk) in state.iter_mut().zip(digest.chunks_exact(4)) {
+            *word = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        Some(state)
+    }
+
+    /// The padding SHA-1 appends to a message of `length` bytes
+    pub fn padding(length: u64) -> Vec<u8> {
+        md_padding(length, SHA1_BLOCK_SIZE, 8)
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.buffer.extend_from_slice(data);
+
+        let full = self.buffer.len() - self.buffer.len() % SHA1_BLOCK_SIZE;
+        for block in self.buffer[..full].chunks_exact(SHA1_BLOCK_SIZE) {
+            compress(&mut self.state, block);
+        }
+        self.buffer.drain(..full);
+    }
+
+    pub fn finalize(mut self) -> Vec<u8> {
+        let padding = Self::padding(self.length);
+        self.update(&padding);
+        debug_assert!(self.buffer.is_empty());
+
+        self.state.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hash for Sha1 {
+    const BLOCK_SIZE: usize = SHA1_BLOCK_SIZE;
+    const OUTPUT_SIZE: usize = SHA1_OUTPUT_SIZE;
+
+    fn new() -> Self {
+        Sha1::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Sha1::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        Sha1::finalize(self)
+    }
+}
+
+/// One-shot SHA-1
+pub fn sha1(data: &[u8]) -> Vec<u8> {
+    Sha1::digest(data)
+}
+
+fn compress(state: &mut [u32; 5], block: &[u8]) {
+    let mut w = [0u32; 80];
+
+    for (i, chunk) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+
+    for (i, &word) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5A827999),
+            20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+            _ => (b ^ c ^ d, 0xCA62C1D6),
+        };
+
+        let temp = a.rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(word);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+    state[4] = state[4].wrapping_add(e);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serialize::to_hex;
+
+    #[test]
+    fn test_sha1_known_answers() {
+        assert_eq!(to_hex(&sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(to_hex(&sha1(b"abc")), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            to_hex(&sha1(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+    }
+
+    #[test]
+    fn test_sha1_streaming_matches_one_shot() {
+        let data = vec![0x61u8; 1000];
+        let mut hasher = Sha1::new();
+        for chunk in data.chunks(7) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), sha1(&data));
+    }
+
+    #[test]
+    fn test_sha1_resume_from_state() {
+        let message = b"comment1=cooking%20MCs;userdata=foo";
+        let suffix = b";admin=true";
+
+        let mut glued = message.to_vec();
+        glued.extend_from_slice(&Sha1::padding(message.len() as u64));
+        let resumed_length = glued.len() as u64;
+        glued.extend_from_slice(suffix);
+
+        let state = Sha1::state_from_digest(&sha1(message)).unwrap();
+        let mut hasher = Sha1::from_state(state, resumed_length);
+        hasher.update(suffix);
+
+        assert_eq!(hasher.finalize(), sha1(&glued));
+    }
+}
@@ -0,0 +1,212 @@
+// crypto/src/hash/sha256.rs
+use super::{md_padding, Hash};
+
+const SHA256_BLOCK_SIZE: usize = 64;
+const SHA256_OUTPUT_SIZE: usize = 32;
+const SHA224_OUTPUT_SIZE: usize = 28;
+
+const SHA256_INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SHA224_INITIAL_STATE: [u32; 8] = [
+    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+];
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+#[derive(Debug, Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    buffer: Vec<u8>,
+    length: u64,
+}
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Self::from_state(SHA256_INITIAL_STATE, 0)
+    }
+
+    /// Resume hashing from a known internal state; `length` is the number of
+    /// bytes already absorbed, padding included.
+    pub fn from_state(state: [u32; 8], length: u64) -> Self {
+        Self {
+            state,
+            buffer: Vec::with_capacity(SHA256_BLOCK_SIZE),
+            length,
+        }
+    }
+
+    /// Split a digest back into the eight chaining words
+    pub fn state_from_digest(digest: &[u8]) -> Option<[u32; 8]> {
+        if digest.len() != SHA256_OUTPUT_SIZE {
+            return None;
+        }
+
+        let mut state = [0u32; 8];
+        for (word, chunk) in state.iter_mut().zip(digest.chunks_exact(4)) {
+            *word = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        Some(state)
+    }
+
+    /// The padding SHA-224/256 append to a message of `length` bytes
+    pub fn padding(length: u64) -> Vec<u8> {
+        md_padding(length, SHA256_BLOCK_SIZE, 8)
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.length += data.len() as u64;
+        self.buffer.extend_from_slice(data);
+
+        let full = self.buffer.len() - self.buffer.len() % SHA256_BLOCK_SIZE;
+        for block in self.buffer[..full].chunks_exact(SHA256_BLOCK_SIZE) {
+            compress(&mut self.state, block);
+        }
+        self.buffer.drain(..full);
+    }
+
+    pub fn finalize(mut self) -> Vec<u8> {
+        let padding = Self::padding(self.length);
+        self.update(&padding);
+        debug_assert!(self.buffer.is_empty());
+
+        self.state.iter().flat_map(|word| word.to_be_bytes()).collect()
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hash for Sha256 {
+    const BLOCK_SIZE: usize = SHA256_BLOCK_SIZE;
+    const OUTPUT_SIZE: usize = SHA256_OUTPUT_SIZE;
+
+    fn new() -> Self {
+        Sha256::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Sha256::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        Sha256::finalize(self)
+    }
+}
+
+/// SHA-224 is SHA-256 with a different IV and a truncated output
+#[derive(Debug, Clone)]
+pub struct Sha224 {
+    inner: Sha256,
+}
+
+impl Sha224 {
+    pub fn new() -> Self {
+        Self::from_state(SHA224_INITIAL_STATE, 0)
+    }
+
+    pub fn from_state(state: [u32; 8], length: u64) -> Self {
+        Self { inner: Sha256::from_state(state, length) }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data)
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        let mut digest = self.inner.finalize();
+        digest.truncate(SHA224_OUTPUT_SIZE);
+        digest
+    }
+}
+
+impl Default for Sha224 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hash for Sha224 {
+    const BLOCK_SIZE: usize = SHA256_BLOCK_SIZE;
+    const OUTPUT_SIZE: usize = SHA224_OUTPUT_SIZE;
+
+    fn new() -> Self {
+        Sha224::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Sha224::update(self, data)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        Sha224::finalize(self)
+    }
+}
+
+/// One-shot SHA-224
+pub fn sha224(data: &[u8]) -> Vec<u8> {
+    Sha224::digest(data)
+}
+
+/// One-shot SHA-256
+pub fn sha256(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data)
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    let mut w = [0u32; 64];
+
+    for (i, chunk) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes(chunk.try_into().unwrap());
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    for (&word, &k) in w.iter().zip(K.iter()) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(k)
+            .wrapping_add(word);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+}
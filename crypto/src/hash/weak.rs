@@ -0,0 +1,73 @@
+// crypto/src/hash/weak.rs
+use crate::aes::block::encrypt_block;
+
+use super::md_padding;
+
+pub const WEAK_BLOCK_SIZE: usize = 16;
+
+/// A deliberately breakable Merkle-Damgård hash: the chaining value is the AES
+/// key, each message block is encrypted under it, and the result is truncated
+/// back to a `bits`-wide state. Birthday collisions cost about 2^(bits/2)
+/// compressions, so anything from 16 to 32 bits is attackable in seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeakHash {
+    bits: u32,
+}
+
+impl WeakHash {
+    pub fn new(bits: u32) -> Self {
+        assert!((16..=32).contains(&bits) && bits.is_multiple_of(8), "state must be 16, 24 or 32 bits");
+        Self { bits }
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    pub fn initial_state(&self) -> u32 {
+        0x0123_4567 >> (32 - self.bits)
+    }
+
+    pub fn compress(&self, state: u32, block: &[u8]) -> u32 {
+        let width = self.bits as usize / 8;
+        let mut key = [0u8; 16];
+        key[..width].copy_from_slice(&state.to_be_bytes()[4 - width..]);
+
+        let encrypted = encrypt_block(block, &key).expect("16-byte block and key");
+        encrypted[..width].iter().fold(0, |acc, &b| (acc << 8) | b as u32)
+    }
+
+    /// Runs whole blocks through the compression function, without padding
+    pub fn chain(&self, state: u32, blocks: &[u8]) -> u32 {
+        debug_assert!(blocks.len().is_multiple_of(WEAK_BLOCK_SIZE));
+        blocks.chunks_exact(WEAK_BLOCK_SIZE).fold(state, |state, block| self.compress(state, block))
+    }
+
+    /// Applies the length padding to a state reached after `length` block-aligned bytes
+    pub fn finish(&self, state: u32, length: u64) -> u32 {
+        self.chain(state, &md_padding(length, WEAK_BLOCK_SIZE, 8))
+    }
+
+    pub fn hash(&self, message: &[u8]) -> u32 {
+        let padded = [message, &md_padding(message.len() as u64, WEAK_BLOCK_SIZE, 8)].concat();
+        self.chain(self.initial_state(), &padded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_width() {
+        for bits in [16, 24, 32] {
+            let hash = WeakHash::new(bits);
+            assert!(u64::from(hash.hash(b"hello")) < 1 << bits);
+            assert_ne!(hash.hash(b"hello"), hash.hash(b"hellp"));
+        }
+
+        let hash = WeakHash::new(16);
+        let message = [7u8; 48];
+        assert_eq!(hash.hash(&message), hash.finish(hash.chain(hash.initial_state(), &message), 48));
+    }
+}
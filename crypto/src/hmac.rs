@@ -0,0 +1,65 @@
+// crypto/src/hmac.rs
+use crate::hash::{Hash, Sha1, Sha256};
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+/// HMAC (RFC 2104) over any of the crate's hashes
+pub fn hmac<H: Hash>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut block_key = if key.len() > H::BLOCK_SIZE {
+        H::digest(key)
+    } else {
+        key.to_vec()
+    };
+    block_key.resize(H::BLOCK_SIZE, 0);
+
+    let mut inner = H::new();
+    inner.update(&block_key.iter().map(|b| b ^ IPAD).collect::<Vec<u8>>());
+    inner.update(message);
+    let inner_digest = inner.finalize();
+
+    let mut outer = H::new();
+    outer.update(&block_key.iter().map(|b| b ^ OPAD).collect::<Vec<u8>>());
+    outer.update(&inner_digest);
+    outer.finalize()
+}
+
+pub fn hmac_sha1(key: &[u8], message: &[u8]) -> Vec<u8> {
+    hmac::<Sha1>(key, message)
+}
+
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    hmac::<Sha256>(key, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serialize::to_hex;
+
+    // RFC 2202 test cases 1, 2 and 6
+    #[test]
+    fn test_hmac_sha1_rfc2202() {
+        assert_eq!(
+            to_hex(&hmac_sha1(&[0x0b; 20], b"Hi There")),
+            "b617318655057264e28bc0b6fb378c8ef146be00"
+        );
+        assert_eq!(
+            to_hex(&hmac_sha1(b"Jefe", b"what do ya want for nothing?")),
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79"
+        );
+        assert_eq!(
+            to_hex(&hmac_sha1(&[0xaa; 80], b"Test Using Larger Than Block-Size Key - Hash Key First")),
+            "aa4ae5e15272d00e95705637ce8a3b55ed402112"
+        );
+    }
+
+    // RFC 4231 test case 2
+    #[test]
+    fn test_hmac_sha256_rfc4231() {
+        assert_eq!(
+            to_hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
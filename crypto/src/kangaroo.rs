@@ -0,0 +1,55 @@
+// crypto/src/kangaroo.rs
+use crate::bigint::BigUint;
+
+/// The little a cyclic group has to offer for Pollard's kangaroo, written
+/// multiplicatively: g^k for a fixed base g, the group operation, and a
+/// deterministic pseudo-random map from elements to jump sizes
+pub trait KangarooGroup {
+    type Element: Clone + PartialEq;
+
+    fn power(&self, k: &BigUint) -> Self::Element;
+    fn combine(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    fn index(&self, element: &Self::Element, k: u32) -> u32;
+}
+
+/// Pollard's lambda method: finds x in [a, b] with g^x = y in about
+/// 2 * sqrt(b - a) group operations. A tame kangaroo jumps from g^b and leaves
+/// a trap; a wild one starting at y lands in it if the two paths ever merge.
+/// A miss is retried with a different jump function a few times.
+pub fn kangaroo<G: KangarooGroup>(group: &G, y: &G::Element, a: &BigUint, b: &BigUint) -> Option<BigUint> {
+    let width: u128 = (b - a).try_into().ok()?;
+    let root = width.isqrt().max(1);
+
+    // Smallest k whose mean jump (2^k - 1) / k reaches sqrt(width) / 2
+    let mut k = 1u32;
+    while ((1u128 << k) - 1) / (k as u128) < root / 2 {
+        k += 1;
+    }
+
+    for attempt in 0..4 {
+        let k = k + attempt;
+        let jumps: Vec<G::Element> = (0..k).map(|i| group.power(&(BigUint::from(1u32) << i))).collect();
+        let steps = 4 * ((1u128 << k) - 1) / k as u128;
+
+        let mut tame = group.power(b);
+        let mut tame_distance = 0u128;
+        for _ in 0..steps {
+            let i = group.index(&tame, k);
+            tame_distance += 1 << i;
+            tame = group.combine(&tame, &jumps[i as usize]);
+        }
+
+        let mut wild = y.clone();
+        let mut wild_distance = 0u128;
+        while wild_distance <= width + tame_distance {
+            if wild == tame {
+                return Some(b + tame_distance - wild_distance);
+            }
+            let i = group.index(&wild, k);
+            wild_distance += 1 << i;
+            wild = group.combine(&wild, &jumps[i as usize]);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,220 @@
+// crypto/src/kat/cavp.rs
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serialize::from_hex;
+
+use super::{digest_for, Algorithm, Tally};
+use crate::aes::{aes128_decrypt, aes128_encrypt, ctr, gcm, AesMode};
+
+/// One `[...]` section of a response file, with its records of
+/// `NAME = value` lines
+struct Section {
+    name: String,
+    records: Vec<HashMap<String, String>>,
+}
+
+impl Section {
+    /// The number in a `[L = 32]` style header
+    fn length(&self) -> Option<usize> {
+        self.name.replace(' ', "").strip_prefix("L=")?.parse().ok()
+    }
+}
+
+fn parse(content: &str) -> Vec<Section> {
+    let mut sections = vec![Section { name: String::new(), records: Vec::new() }];
+    let mut record = HashMap::new();
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('#') {
+            continue;
+        }
+        if line.is_empty() || line.starts_with('[') {
+            let section = sections.last_mut().unwrap();
+            if !record.is_empty() {
+                section.records.push(std::mem::take(&mut record));
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+                sections.push(Section { name: name.to_string(), records: Vec::new() });
+            }
+        } else if let Some((name, value)) = line.split_once('=') {
+            record.insert(name.trim().to_string(), value.trim().to_string());
+        } else if line == "FAIL" {
+            // GCM decrypt files mark a record whose tag must not verify
+            record.insert(line.to_string(), String::new());
+        }
+    }
+    if !record.is_empty() {
+        sections.last_mut().unwrap().records.push(record);
+    }
+
+    sections
+}
+
+fn hex(record: &HashMap<String, String>, name: &str) -> Vec<u8> {
+    from_hex(&record[name]).unwrap_or_else(|e| panic!("{} is not hex: {}", name, e))
+}
+
+fn number(record: &HashMap<String, String>, name: &str) -> usize {
+    record[name].parse().unwrap_or_else(|e| panic!("{} is not a number: {}", name, e))
+}
+
+/// Runs `path` if it holds `algorithm` vectors, going by the NIST file naming.
+/// SHA-512/t and SHA-3 files (`SHA512_256ShortMsg.rsp`, `SHA3_256...`) reuse
+/// the SHA-2 section headers for other hashes, so they are left alone.
+pub(super) fn run(path: &Path, algorithm: Algorithm) -> Option<Tally> {
+    let name = path.file_name()?.to_str()?;
+    let file_algorithm = match name {
+        _ if name.starts_with("ECB") => Algorithm::AesEcb,
+        _ if name.starts_with("CBC") => Algorithm::AesCbc,
+        _ if name.starts_with("CTR") => Algorithm::AesCtr,
+        _ if name.starts_with("gcm") => Algorithm::AesGcm,
+        _ if name.starts_with("SHA") && !name.contains('_') => Algorithm::Sha,
+        _ if name.starts_with("HMAC") => Algorithm::Hmac,
+        _ => return None,
+    };
+    if file_algorithm != algorithm {
+        return None;
+    }
+
+    let content = fs::read_to_string(path).expect("Failed to read vector file");
+    let mut tally = Tally::default();
+    for section in parse(&content) {
+        match algorithm {
+            Algorithm::Sha => sha(&section, &mut tally),
+            Algorithm::Hmac => hmac(&section, &mut tally),
+            Algorithm::AesGcm => aes_gcm(&section, &mut tally),
+            _ => aes(&section, algorithm, &mut tally),
+        }
+    }
+    Some(tally)
+}
+
+/// How an AESVS record is run
+enum Mode {
+    Aes(AesMode),
+    /// An SP 800-38A initial counter block
+    Counter([u8; 16]),
+}
+
+/// AESVS: `[ENCRYPT]` and `[DECRYPT]` sections of KEY, IV, PLAINTEXT and
+/// CIPHERTEXT. CTR files give the initial SP 800-38A counter block as the IV,
+/// so they run through the big-endian counter path rather than `AesMode::CTR`.
+fn aes(section: &Section, algorithm: Algorithm, tally: &mut Tally) {
+    let encrypt = match section.name.as_str() {
+        "ENCRYPT" => true,
+        "DECRYPT" => false,
+        _ => return,
+    };
+
+    for record in &section.records {
+        let key = hex(record, "KEY");
+        let mode = match algorithm {
+            Algorithm::AesEcb => Some(Mode::Aes(AesMode::ECB)),
+            Algorithm::AesCbc => hex(record, "IV").try_into().ok().map(|iv| Mode::Aes(AesMode::CBC { iv })),
+            _ => hex(record, "IV").try_into().ok().map(Mode::Counter),
+        };
+        let Some(mode) = mode.filter(|_| key.len() == 16) else {
+            tally.skip();
+            continue;
+        };
+
+        let (plaintext, ciphertext) = (hex(record, "PLAINTEXT"), hex(record, "CIPHERTEXT"));
+        let (input, expected) = if encrypt { (plaintext, ciphertext) } else { (ciphertext, plaintext) };
+        let output = match mode {
+            Mode::Aes(mode) if encrypt => aes128_encrypt(&input, &key, mode),
+            Mode::Aes(mode) => aes128_decrypt(&input, &key, mode),
+            // Encryption and decryption are the same operation
+            Mode::Counter(counter_block) => ctr::apply_big_endian(&input, &key, &counter_block),
+        };
+        let ok = output.ok() == Some(expected);
+        tally.check(ok, || format!("{} COUNT = {}", section.name, record["COUNT"]));
+    }
+}
+
+/// GCMVS: records of Key, IV, PT, AAD, CT and Tag under `[Keylen = ...]`
+/// style headers. Decrypt files replace PT with FAIL where the tag is bad.
+/// Only AES-128 with 96-bit IVs and full tags fits the crate's GCM.
+fn aes_gcm(section: &Section, tally: &mut Tally) {
+    for record in &section.records {
+        let (key, aad, tag) = (hex(record, "Key"), hex(record, "AAD"), hex(record, "Tag"));
+        let nonce: Option<[u8; gcm::GCM_NONCE_SIZE]> = hex(record, "IV").try_into().ok();
+        let Some(nonce) = nonce.filter(|_| key.len() == 16 && tag.len() == gcm::GCM_TAG_SIZE) else {
+            tally.skip();
+            continue;
+        };
+
+        let mut sealed = hex(record, "CT");
+        sealed.extend_from_slice(&tag);
+        let opened = gcm::open(&sealed, &key, &nonce, &aad).ok();
+        let ok = if record.contains_key("FAIL") {
+            opened.is_none()
+        } else {
+            let plaintext = hex(record, "PT");
+            opened.as_ref() == Some(&plaintext) && gcm::seal(&plaintext, &key, &nonce, &aad).ok() == Some(sealed)
+        };
+        tally.check(ok, || format!("[{}] Count = {}", section.name, record["Count"]));
+    }
+}
+
+/// SHAVS: `[L = n]` sections of Len (in bits), Msg and MD
+fn sha(section: &Section, tally: &mut Tally) {
+    let digest = section.length().and_then(digest_for);
+
+    for record in &section.records {
+        let len = number(record, "Len");
+        let Some((digest, _)) = digest.filter(|_| len.is_multiple_of(8)) else {
+            tally.skip();
+            continue;
+        };
+
+        let mut msg = hex(record, "Msg");
+        msg.truncate(len / 8);
+        tally.check(digest(&msg) == hex(record, "MD"), || format!("[{}] Len = {}", section.name, len));
+    }
+}
+
+/// HMACVS: `[L=n]` sections of Klen, Tlen, Key, Msg and a Mac truncated to
+/// Tlen bytes
+fn hmac(section: &Section, tally: &mut Tally) {
+    let Some((_, mac)) = section.length().and_then(digest_for) else {
+        section.records.iter().for_each(|_| tally.skip());
+        return;
+    };
+
+    for record in &section.records {
+        let tlen = number(record, "Tlen");
+        let expected = hex(record, "Mac");
+        let computed = mac(&hex(record, "Key"), &hex(record, "Msg"));
+        let ok = expected.len() == tlen && computed.get(..tlen) == Some(expected.as_slice());
+        tally.check(ok, || format!("[{}] Count = {}", section.name, record["Count"]));
+    }
+}
+
+#[test]
+fn test_parse() {
+    let sections = parse("# comment\n\n[L = 20]\n\nLen = 0\nMsg = 00\n\nLen = 8\nMsg = 37\n[ENCRYPT]\nCOUNT = 0\n");
+    assert_eq!(sections.len(), 3);
+    assert_eq!(sections[1].length(), Some(20));
+    assert_eq!(sections[1].records.len(), 2);
+    assert_eq!(sections[1].records[1]["Msg"], "37");
+    assert_eq!(sections[2].name, "ENCRYPT");
+    assert_eq!(sections[2].records[0]["COUNT"], "0");
+}
+
+#[test]
+fn test_gcm_fail_record() {
+    // Test case 1 of the GCM spec, then the same with its tag's last bit flipped
+    let record = "Count = 0\nKey = 00000000000000000000000000000000\nIV = 000000000000000000000000\nCT = \nAAD = \n";
+    let content = format!(
+        "[Taglen = 128]\n\n{0}Tag = 58e2fccefa7e3061367f1d57a4e7455a\nPT = \n\n{0}Tag = 58e2fccefa7e3061367f1d57a4e7455b\nFAIL\n",
+        record
+    );
+
+    let mut tally = Tally::default();
+    for section in parse(&content) {
+        aes_gcm(&section, &mut tally);
+    }
+    assert_eq!((tally.passed, tally.failed.len(), tally.skipped), (2, 0, 0));
+}
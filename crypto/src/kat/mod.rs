@@ -0,0 +1,157 @@
+// crypto/src/kat/mod.rs
+//! Known-answer tests: NIST CAVP `.rsp` files and Wycheproof JSON under
+//! data/vectors/, run against the crate's primitives. Each algorithm is its
+//! own test, so `cargo test -p crypto kat::test_aes_cbc` runs only the CBC
+//! files from both sources; add `-- --nocapture` for the per-file counts.
+//! The files checked in are the SHAVS responses and the Wycheproof AES-GCM,
+//! AES-CBC-PKCS5 and HMAC-SHA256 files (some only in part), and published
+//! examples (AESAVS, FIPS 197 and 180, SP 800-38A, the GCM spec, RFCs 2202,
+//! 3602, 3686 and 4231) in the CAVP layout; the other official CAVP responses
+//! and upstream Wycheproof files run unchanged when copied in beside them.
+mod cavp;
+mod wycheproof;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::hash::{Hash, Sha1, Sha224, Sha256, Sha384, Sha512};
+use crate::hmac::hmac;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Algorithm {
+    AesEcb,
+    AesCbc,
+    AesCtr,
+    AesGcm,
+    Sha,
+    Hmac,
+}
+
+/// How the vectors in one file fared
+#[derive(Debug, Default)]
+struct Tally {
+    passed: usize,
+    /// Labels of the vectors that failed
+    failed: Vec<String>,
+    /// Vectors for parameters the crate doesn't implement, e.g. AES-256
+    skipped: usize,
+}
+
+impl Tally {
+    fn check(&mut self, ok: bool, label: impl FnOnce() -> String) {
+        if ok {
+            self.passed += 1;
+        } else {
+            self.failed.push(label());
+        }
+    }
+
+    fn skip(&mut self) {
+        self.skipped += 1;
+    }
+}
+
+type DigestFn = fn(&[u8]) -> Vec<u8>;
+type MacFn = fn(&[u8], &[u8]) -> Vec<u8>;
+
+/// The hash with this output size in bytes, which is how both SHAVS and
+/// HMACVS name them
+fn digest_for(output_size: usize) -> Option<(DigestFn, MacFn)> {
+    match output_size {
+        20 => Some((Sha1::digest, hmac::<Sha1>)),
+        28 => Some((Sha224::digest, hmac::<Sha224>)),
+        32 => Some((Sha256::digest, hmac::<Sha256>)),
+        48 => Some((Sha384::digest, hmac::<Sha384>)),
+        64 => Some((Sha512::digest, hmac::<Sha512>)),
+        _ => None,
+    }
+}
+
+/// Every file under data/vectors, in a stable order
+fn vector_files() -> Vec<PathBuf> {
+    fn walk(dir: &Path, files: &mut Vec<PathBuf>) {
+        for entry in fs::read_dir(dir).expect("Failed to read vector directory") {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                walk(&path, files);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    walk(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../data/vectors"), &mut files);
+    files.sort();
+    files
+}
+
+/// Runs every file for `algorithm`, printing a line per file, and fails if
+/// any vector did, if there were none to run, or if a file had nothing the
+/// crate could run, since that usually means its format was misread
+fn run(algorithm: Algorithm) {
+    let (mut files, mut passed, mut failed) = (0, 0, 0);
+    let mut unused = Vec::new();
+
+    for path in vector_files() {
+        let tally = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("rsp") => cavp::run(&path, algorithm),
+            Some("json") => wycheproof::run(&path, algorithm),
+            _ => None,
+        };
+        let Some(tally) = tally else { continue };
+
+        let name = path.file_name().unwrap().to_string_lossy();
+        println!(
+            "{:<28} {:>5} passed {:>3} failed {:>3} skipped",
+            name,
+            tally.passed,
+            tally.failed.len(),
+            tally.skipped
+        );
+        for label in &tally.failed {
+            println!("    FAIL {}", label);
+        }
+        if tally.passed == 0 {
+            unused.push(name.into_owned());
+        }
+
+        files += 1;
+        passed += tally.passed;
+        failed += tally.failed.len();
+    }
+
+    assert!(files > 0 && passed > 0, "no {:?} vectors under data/vectors", algorithm);
+    assert_eq!(failed, 0, "{} of {} {:?} vectors failed", failed, passed + failed, algorithm);
+    assert!(unused.is_empty(), "no {:?} vector passed in {}", algorithm, unused.join(", "));
+}
+
+#[test]
+fn test_aes_ecb() {
+    run(Algorithm::AesEcb);
+}
+
+#[test]
+fn test_aes_cbc() {
+    run(Algorithm::AesCbc);
+}
+
+#[test]
+fn test_aes_ctr() {
+    run(Algorithm::AesCtr);
+}
+
+#[test]
+fn test_aes_gcm() {
+    run(Algorithm::AesGcm);
+}
+
+#[test]
+fn test_sha() {
+    run(Algorithm::Sha);
+}
+
+#[test]
+fn test_hmac() {
+    run(Algorithm::Hmac);
+}
@@ -0,0 +1,144 @@
+// crypto/src/kat/wycheproof.rs
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+use serialize::from_hex;
+
+use super::{digest_for, Algorithm, Tally};
+use crate::aes::{aes128_decrypt, aes128_encrypt, gcm, pkcs7_pad, pkcs7_unpad, AesMode};
+
+fn hex(test: &Value, name: &str) -> Vec<u8> {
+    let text = test[name].as_str().unwrap_or_else(|| panic!("missing {}", name));
+    from_hex(text).unwrap_or_else(|e| panic!("{} is not hex: {}", name, e))
+}
+
+fn size(group: &Value, name: &str) -> Option<usize> {
+    group[name].as_u64().map(|bits| bits as usize)
+}
+
+/// Runs `path` if its `algorithm` field names one of ours. Each test is
+/// `valid`, `invalid` or `acceptable`; valid ones must round-trip, invalid
+/// ones must be rejected, and acceptable ones may go either way so are skipped.
+pub(super) fn run(path: &Path, algorithm: Algorithm) -> Option<Tally> {
+    let content = fs::read_to_string(path).expect("Failed to read vector file");
+    tally(&serde_json::from_str(&content).expect("Vector file is not JSON"), algorithm)
+}
+
+fn tally(file: &Value, algorithm: Algorithm) -> Option<Tally> {
+    let name = file["algorithm"].as_str()?;
+    let file_algorithm = match name {
+        "AES-GCM" => Algorithm::AesGcm,
+        "AES-CBC-PKCS5" => Algorithm::AesCbc,
+        _ if name.starts_with("HMACSHA") => Algorithm::Hmac,
+        _ => return None,
+    };
+    if file_algorithm != algorithm {
+        return None;
+    }
+
+    let mut tally = Tally::default();
+    for group in file["testGroups"].as_array().expect("testGroups is not an array") {
+        for test in group["tests"].as_array().expect("tests is not an array") {
+            let accepted = match algorithm {
+                Algorithm::AesGcm => aes_gcm(group, test),
+                Algorithm::AesCbc => aes_cbc_pkcs5(group, test),
+                _ => hmac(name, group, test),
+            };
+
+            let Some(accepted) = accepted else {
+                tally.skip();
+                continue;
+            };
+            let label = || format!("tcId {} ({})", test["tcId"], test["comment"]);
+            match test["result"].as_str() {
+                Some("valid") => tally.check(accepted, label),
+                Some("invalid") => tally.check(!accepted, label),
+                _ => tally.skip(),
+            }
+        }
+    }
+    Some(tally)
+}
+
+/// Whether the tag verifies and sealing the message again gives the same
+/// ciphertext and tag. `None` for anything but AES-128 with 96-bit nonces
+/// and 128-bit tags.
+fn aes_gcm(group: &Value, test: &Value) -> Option<bool> {
+    if (size(group, "keySize")?, size(group, "ivSize")?, size(group, "tagSize")?) != (128, 96, 128) {
+        return None;
+    }
+
+    let (key, aad, msg) = (hex(test, "key"), hex(test, "aad"), hex(test, "msg"));
+    let nonce: [u8; gcm::GCM_NONCE_SIZE] = hex(test, "iv").try_into().ok()?;
+    let mut sealed = hex(test, "ct");
+    sealed.extend_from_slice(&hex(test, "tag"));
+
+    let opened = gcm::open(&sealed, &key, &nonce, &aad).ok() == Some(msg.clone());
+    Some(opened && gcm::seal(&msg, &key, &nonce, &aad).ok() == Some(sealed))
+}
+
+/// Whether the ciphertext decrypts and unpads to the message, and the
+/// message encrypts back to it
+fn aes_cbc_pkcs5(group: &Value, test: &Value) -> Option<bool> {
+    if size(group, "keySize")? != 128 {
+        return None;
+    }
+
+    let (key, msg, ct) = (hex(test, "key"), hex(test, "msg"), hex(test, "ct"));
+    let mode = AesMode::CBC { iv: hex(test, "iv").try_into().ok()? };
+
+    let decrypted = aes128_decrypt(&ct, &key, mode.clone()).ok().and_then(|padded| pkcs7_unpad(&padded).ok());
+    let encrypted = aes128_encrypt(&pkcs7_pad(&msg, 16), &key, mode).ok();
+    Some(decrypted == Some(msg) && encrypted == Some(ct))
+}
+
+/// Whether the tag, truncated to the group's tag size, matches
+fn hmac(algorithm: &str, group: &Value, test: &Value) -> Option<bool> {
+    let output_size = match algorithm {
+        "HMACSHA1" => 20,
+        "HMACSHA224" => 28,
+        "HMACSHA256" => 32,
+        "HMACSHA384" => 48,
+        "HMACSHA512" => 64,
+        _ => return None,
+    };
+    let (_, mac) = digest_for(output_size)?;
+    let tag_size = size(group, "tagSize")? / 8;
+
+    let tag = hex(test, "tag");
+    let computed = mac(&hex(test, "key"), &hex(test, "msg"));
+    Some(tag.len() == tag_size && computed.get(..tag_size) == Some(tag.as_slice()))
+}
+
+#[test]
+fn test_tally() {
+    // Test case 2 of the GCM spec, the same with a flipped tag bit, and a
+    // 256-bit key group the crate can't run
+    let file: Value = serde_json::from_str(
+        r#"{
+            "algorithm": "AES-GCM",
+            "testGroups": [
+                {
+                    "keySize": 128, "ivSize": 96, "tagSize": 128,
+                    "tests": [
+                        { "tcId": 1, "comment": "", "key": "00000000000000000000000000000000",
+                          "iv": "000000000000000000000000", "aad": "", "msg": "00000000000000000000000000000000",
+                          "ct": "0388dace60b6a392f328c2b971b2fe78", "tag": "ab6e47d42cec13bdf53a67b21257bddf",
+                          "result": "valid" },
+                        { "tcId": 2, "comment": "modified tag", "key": "00000000000000000000000000000000",
+                          "iv": "000000000000000000000000", "aad": "", "msg": "00000000000000000000000000000000",
+                          "ct": "0388dace60b6a392f328c2b971b2fe78", "tag": "ab6e47d42cec13bdf53a67b21257bdde",
+                          "result": "invalid" }
+                    ]
+                },
+                { "keySize": 256, "ivSize": 96, "tagSize": 128, "tests": [{ "tcId": 3 }] }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let counts = tally(&file, Algorithm::AesGcm).unwrap();
+    assert_eq!((counts.passed, counts.failed.len(), counts.skipped), (2, 0, 1));
+    assert!(tally(&file, Algorithm::AesCbc).is_none());
+}
@@ -1,6 +1,22 @@
 pub mod aes;
+pub mod bigint;
+pub mod cbc_mac;
 pub mod common;
+pub mod compression;
+pub mod dh;
+pub mod dsa;
+pub mod ec;
+pub mod gf2_128;
+pub mod hash;
+pub mod hmac;
+pub mod kangaroo;
+#[cfg(test)]
+mod kat;
+pub mod rsa;
+pub mod srp;
 pub mod utils;
 
 pub use aes::{aes128_encrypt, aes128_decrypt, AesMode};
-pub use common::AesError;
\ No newline at end of file
+pub use common::{AesError, DhError, DsaError, EcError, MacError, RsaError, SrpError};
+pub use hash::{sha1, sha224, sha256, sha384, sha512, Sha1, Sha224, Sha256, Sha384, Sha512};
+pub use hmac::{hmac_sha1, hmac_sha256};
@@ -0,0 +1,170 @@
+// crypto/src/rsa/attacks.rs
+use std::collections::HashSet;
+
+use crate::bigint::{crt, exact_nth_root, modinv, random_range, BigUint, One, Zero};
+use crate::common::RsaError;
+
+use super::{RsaPrivateKey, RsaPublicKey};
+
+/// Håstad's broadcast attack: the same m encrypted under e keys with exponent e.
+/// CRT gives m^e mod n1*n2*...*ne, which is m^e itself since m < every ni,
+/// so an integer e-th root recovers m.
+pub fn hastad_broadcast(ciphertexts: &[(BigUint, RsaPublicKey)]) -> Option<BigUint> {
+    let e = &ciphertexts.first()?.1.e;
+    let e_small: u32 = e.try_into().ok()?;
+
+    if ciphertexts.len() < e_small as usize || ciphertexts.iter().any(|(_, key)| key.e != *e) {
+        return None;
+    }
+
+    let (residues, moduli): (Vec<BigUint>, Vec<BigUint>) = ciphertexts.iter()
+        .take(e_small as usize)
+        .map(|(c, key)| (c.clone(), key.n.clone()))
+        .unzip();
+
+    let (m_to_the_e, _) = crt(&residues, &moduli)?;
+    exact_nth_root(&m_to_the_e, e_small)
+}
+
+/// Decrypts anything once, refusing ciphertexts it has already seen
+pub struct DecryptionOracle {
+    private: RsaPrivateKey,
+    seen: HashSet<BigUint>,
+}
+
+impl DecryptionOracle {
+    pub fn new(private: RsaPrivateKey) -> Self {
+        Self { private, seen: HashSet::new() }
+    }
+
+    pub fn public_key(&self) -> RsaPublicKey {
+        self.private.public_key()
+    }
+
+    pub fn decrypt(&mut self, c: &BigUint) -> Result<BigUint, RsaError> {
+        if !self.seen.insert(c.clone()) {
+            return Err(RsaError::RefusedCiphertext);
+        }
+        self.private.decrypt(c)
+    }
+}
+
+/// Recover the plaintext of an already-submitted ciphertext by blinding it:
+/// decrypt C' = S^e * C, then divide the result by S.
+pub fn unpadded_message_recovery(oracle: &mut DecryptionOracle, c: &BigUint) -> Result<BigUint, RsaError> {
+    let public = oracle.public_key();
+    let n = &public.n;
+
+    loop {
+        let s = random_range(&BigUint::from(2u32), n);
+        // S has to be invertible to undo the blinding
+        let Some(s_inverse) = modinv(&s, n) else {
+            continue;
+        };
+
+        let blinded = (s.modpow(&public.e, n) * c) % n;
+        let p = oracle.decrypt(&blinded)?;
+        return Ok((p * s_inverse) % n);
+    }
+}
+
+/// Decrypts and reveals only whether the plaintext is even
+pub struct ParityOracle {
+    private: RsaPrivateKey,
+    pub queries: usize,
+}
+
+impl ParityOracle {
+    pub fn new(private: RsaPrivateKey) -> Self {
+        Self { private, queries: 0 }
+    }
+
+    pub fn public_key(&self) -> RsaPublicKey {
+        self.private.public_key()
+    }
+
+    pub fn is_even(&mut self, c: &BigUint) -> bool {
+        self.queries += 1;
+        self.private.decrypt(c).map(|m| !m.bit(0)).unwrap_or(false)
+    }
+}
+
+/// Recover m from a parity oracle by repeatedly doubling the plaintext.
+///
+/// Since n is odd, 2m mod n is even exactly when 2m didn't wrap, i.e. when
+/// m < n/2. Each answer halves the interval [k * n / 2^i, (k + 1) * n / 2^i)
+/// known to contain m, and `on_progress` sees its upper bound as bytes.
+pub fn parity_attack<O, F>(public: &RsaPublicKey, c: &BigUint, mut is_even: O, mut on_progress: F) -> BigUint
+where
+    O: FnMut(&BigUint) -> bool,
+    F: FnMut(&[u8]),
+{
+    let n = &public.n;
+    let doubler = BigUint::from(2u32).modpow(&public.e, n);
+
+    let mut c = c.clone();
+    let mut k = BigUint::zero();
+
+    for i in 1..=n.bits() {
+        c = (c * &doubler) % n;
+        k <<= 1u32;
+        if !is_even(&c) {
+            k += 1u32;
+        }
+
+        let upper = ((&k + BigUint::one()) * n) >> i;
+        on_progress(&upper.to_bytes_be());
+    }
+
+    ((k + BigUint::one()) * n) >> n.bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rsa::RsaKeyPair;
+    use serialize::from_base64;
+
+    #[test]
+    fn test_hastad_broadcast_e3() {
+        let m = BigUint::from_bytes_be(b"Three keys are worse than one");
+        let ciphertexts: Vec<(BigUint, RsaPublicKey)> = (0..3)
+            .map(|_| {
+                let keys = RsaKeyPair::generate(256, 3);
+                (keys.public.encrypt(&m).unwrap(), keys.public)
+            })
+            .collect();
+
+        assert_eq!(hastad_broadcast(&ciphertexts), Some(m));
+        assert_eq!(hastad_broadcast(&ciphertexts[..2]), None);
+    }
+
+    #[test]
+    fn test_unpadded_message_recovery() {
+        let keys = RsaKeyPair::generate(512, 65537);
+        let mut oracle = DecryptionOracle::new(keys.private);
+
+        let m = BigUint::from_bytes_be(b"{time: 1356304276, social: '555-55-5555'}");
+        let c = keys.public.encrypt(&m).unwrap();
+
+        assert_eq!(oracle.decrypt(&c), Ok(m.clone()));
+        assert_eq!(oracle.decrypt(&c), Err(RsaError::RefusedCiphertext));
+        assert_eq!(unpadded_message_recovery(&mut oracle, &c), Ok(m));
+    }
+
+    #[test]
+    fn test_parity_oracle_attack() {
+        let keys = RsaKeyPair::generate(1024, 65537);
+        let mut oracle = ParityOracle::new(keys.private);
+
+        let message = from_base64("VGhhdCdzIHdoeSBJIGZvdW5kIHlvdSBkb24ndCBwbGF5IGFyb3VuZCB3aXRoIHRoZSBGdW5reSBDb2xkIE1lZGluYQ==").unwrap();
+        let c = keys.public.encrypt(&BigUint::from_bytes_be(&message)).unwrap();
+
+        let mut reveals = Vec::new();
+        let m = parity_attack(&keys.public, &c, |c| oracle.is_even(c), |upper| reveals.push(upper.to_vec()));
+
+        assert_eq!(m.to_bytes_be(), message);
+        assert_eq!(oracle.queries, 1024);
+        assert_eq!(reveals.last().unwrap(), &message);
+    }
+}
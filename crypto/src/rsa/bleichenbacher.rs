@@ -0,0 +1,256 @@
+// crypto/src/rsa/bleichenbacher.rs
+use crate::bigint::{cube_root, modinv, random_range, to_bytes_padded, BigUint, Integer, One, Zero};
+
+use super::pkcs1::{encode_digest, DigestAlgorithm};
+use super::{RsaPrivateKey, RsaPublicKey};
+
+/// Forge a signature for a sloppy verifier with e = 3 (Bleichenbacher '06).
+///
+/// Build 00 01 FF 00 DigestInfo HASH followed by as much garbage as the modulus
+/// allows, and take the integer cube root of the largest such block. The cube
+/// of that root lands inside the range as long as the garbage is longer than
+/// the rounding error, which is about two thirds of the modulus.
+pub fn forge_signature(public: &RsaPublicKey, message: &[u8], alg: DigestAlgorithm) -> Option<Vec<u8>> {
+    if public.e != BigUint::from(3u32) {
+        return None;
+    }
+
+    let k = public.size();
+    let mut prefix = vec![0x00, 0x01, 0xff, 0x00];
+    prefix.extend_from_slice(&encode_digest(alg, message));
+    if prefix.len() >= k {
+        return None;
+    }
+
+    let mut low = prefix.clone();
+    low.resize(k, 0x00);
+    let mut high = prefix;
+    high.resize(k, 0xff);
+
+    let low = BigUint::from_bytes_be(&low);
+    let root = cube_root(&BigUint::from_bytes_be(&high));
+
+    if root.pow(3u32) < low {
+        return None;
+    }
+
+    Some(to_bytes_padded(&root, k))
+}
+
+/// Answers only whether a ciphertext decrypts to a block starting 00 02
+pub struct PaddingOracle {
+    private: RsaPrivateKey,
+    pub queries: usize,
+}
+
+impl PaddingOracle {
+    pub fn new(private: RsaPrivateKey) -> Self {
+        Self { private, queries: 0 }
+    }
+
+    pub fn public_key(&self) -> RsaPublicKey {
+        self.private.public_key()
+    }
+
+    pub fn is_conforming(&mut self, c: &BigUint) -> bool {
+        self.queries += 1;
+
+        match self.private.decrypt(c) {
+            Ok(m) => {
+                let block = to_bytes_padded(&m, self.private.size());
+                block[0] == 0x00 && block[1] == 0x02
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// Bleichenbacher's 1998 attack on a PKCS#1 v1.5 padding oracle.
+/// Returns the full padded plaintext block as an integer.
+pub fn bleichenbacher98<F>(public: &RsaPublicKey, c: &BigUint, mut oracle: F) -> Option<BigUint>
+where
+    F: FnMut(&BigUint) -> bool,
+{
+    let n = &public.n;
+    let k = public.size();
+    let b = BigUint::one() << (8 * (k - 2));
+    let two_b = &b * 2u32;
+    let three_b = &b * 3u32;
+
+    // c * s^e decrypts to m * s
+    let blind = |base: &BigUint, s: &BigUint| (base * s.modpow(&public.e, n)) % n;
+
+    // Step 1: blinding, only needed when c itself isn't conforming
+    let mut s0 = BigUint::one();
+    while !oracle(&blind(c, &s0)) {
+        s0 = random_range(&BigUint::from(2u32), n);
+    }
+    let c0 = blind(c, &s0);
+    let mut conforming = |s: &BigUint| oracle(&blind(&c0, s));
+
+    let mut intervals = vec![(two_b.clone(), &three_b - 1u32)];
+    let mut s = BigUint::zero();
+    let mut i = 1;
+
+    loop {
+        s = if i == 1 {
+            // Step 2a: smallest s >= n / 3B that conforms
+            search_from(n.div_ceil(&three_b), &mut conforming)
+        } else if intervals.len() > 1 {
+            // Step 2b: keep counting up from the last s
+            search_from(&s + 1u32, &mut conforming)
+        } else {
+            // Step 2c: one interval left, so search r and s together
+            let (a, b_high) = &intervals[0];
+            search_single_interval(a, b_high, &s, n, &two_b, &three_b, &mut conforming)?
+        };
+
+        // Step 3: narrow every interval with the new s
+        intervals = narrow(&intervals, &s, n, &two_b, &three_b);
+        if intervals.is_empty() {
+            return None;
+        }
+
+        // Step 4: done once the interval has collapsed to a point
+        if intervals.len() == 1 && intervals[0].0 == intervals[0].1 {
+            let s0_inverse = modinv(&s0, n)?;
+            return Some((&intervals[0].0 * s0_inverse) % n);
+        }
+
+        i += 1;
+    }
+}
+
+fn search_from<F>(mut s: BigUint, conforming: &mut F) -> BigUint
+where
+    F: FnMut(&BigUint) -> bool,
+{
+    while !conforming(&s) {
+        s += 1u32;
+    }
+    s
+}
+
+fn search_single_interval<F>(
+    a: &BigUint,
+    b: &BigUint,
+    previous_s: &BigUint,
+    n: &BigUint,
+    two_b: &BigUint,
+    three_b: &BigUint,
+    conforming: &mut F,
+) -> Option<BigUint>
+where
+    F: FnMut(&BigUint) -> bool,
+{
+    // r >= 2 * (b * s - 2B) / n
+    let mut r = ((b * previous_s - two_b) * 2u32).div_ceil(n);
+
+    loop {
+        // (2B + r * n) / b <= s < (3B + r * n) / a
+        let low = (two_b + &r * n).div_ceil(b);
+        let high = (three_b + &r * n).div_ceil(a);
+
+        let mut s = low;
+        while s < high {
+            if conforming(&s) {
+                return Some(s);
+            }
+            s += 1u32;
+        }
+
+        r += 1u32;
+    }
+}
+
+fn narrow(
+    intervals: &[(BigUint, BigUint)],
+    s: &BigUint,
+    n: &BigUint,
+    two_b: &BigUint,
+    three_b: &BigUint,
+) -> Vec<(BigUint, BigUint)> {
+    let mut narrowed: Vec<(BigUint, BigUint)> = Vec::new();
+
+    for (a, b) in intervals {
+        // (a * s - 3B + 1) / n <= r <= (b * s - 2B) / n
+        let a_s = a * s;
+        let low_numerator = &a_s + 1u32;
+        let r_low = if low_numerator > *three_b {
+            (low_numerator - three_b).div_ceil(n)
+        } else {
+            BigUint::zero()
+        };
+        let b_s = b * s;
+        if b_s < *two_b {
+            continue;
+        }
+        let r_high = (b_s - two_b) / n;
+
+        let mut r = r_low;
+        while r <= r_high {
+            let new_a = a.max(&(two_b + &r * n).div_ceil(s)).clone();
+            let new_b = b.min(&((three_b - 1u32 + &r * n) / s)).clone();
+
+            if new_a <= new_b {
+                insert_merged(&mut narrowed, (new_a, new_b));
+            }
+            r += 1u32;
+        }
+    }
+
+    narrowed
+}
+
+/// Add an interval, merging it with any it overlaps
+fn insert_merged(intervals: &mut Vec<(BigUint, BigUint)>, (mut a, mut b): (BigUint, BigUint)) {
+    let mut i = 0;
+    while i < intervals.len() {
+        let (x, y) = &intervals[i];
+        if *x <= b && a <= *y {
+            a = a.min(x.clone());
+            b = b.max(y.clone());
+            intervals.swap_remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    intervals.push((a, b));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rsa::pkcs1::unpad_type2;
+    use crate::rsa::RsaKeyPair;
+
+    #[test]
+    fn test_forged_signature_fools_sloppy_verifier_only() {
+        let keys = RsaKeyPair::generate(1024, 3);
+
+        let forged = forge_signature(&keys.public, b"hi mom", DigestAlgorithm::Sha1).unwrap();
+        assert!(keys.public.verify_pkcs1_sloppy(b"hi mom", &forged, DigestAlgorithm::Sha1));
+        assert!(!keys.public.verify_pkcs1(b"hi mom", &forged, DigestAlgorithm::Sha1));
+    }
+
+    fn recover(bits: u64, message: &[u8]) {
+        let keys = RsaKeyPair::generate(bits, 3);
+        let mut oracle = PaddingOracle::new(keys.private.clone());
+
+        let c = BigUint::from_bytes_be(&keys.public.encrypt_pkcs1(message).unwrap());
+        let m = bleichenbacher98(&keys.public, &c, |c| oracle.is_conforming(c)).unwrap();
+
+        let block = to_bytes_padded(&m, keys.public.size());
+        assert_eq!(unpad_type2(&block, keys.public.size()).unwrap(), message);
+    }
+
+    #[test]
+    fn test_bleichenbacher98_256_bit() {
+        recover(256, b"kick it, CC");
+    }
+
+    #[test]
+    fn test_bleichenbacher98_768_bit() {
+        recover(768, b"kick it, CC");
+    }
+}
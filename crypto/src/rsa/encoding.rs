@@ -0,0 +1,141 @@
+// crypto/src/rsa/encoding.rs
+use serialize::{Der, Pem, SerializeError, SubjectPublicKeyInfo};
+
+use crate::bigint::{from_der, modinv, to_der, BigUint};
+
+use super::{RsaPublicKey, RsaPrivateKey};
+
+/// rsaEncryption, 1.2.840.113549.1.1.1 (RFC 8017, appendix A.1)
+pub const RSA_ENCRYPTION: &[u64] = &[1, 2, 840, 113549, 1, 1, 1];
+
+fn malformed(what: &str) -> SerializeError {
+    SerializeError::InvalidData(format!("expected {}", what))
+}
+
+impl RsaPublicKey {
+    /// PKCS#1 RSAPublicKey: SEQUENCE { n, e }
+    pub fn to_pkcs1_der(&self) -> Vec<u8> {
+        Der::Sequence(vec![to_der(&self.n), to_der(&self.e)]).to_der()
+    }
+
+    pub fn from_pkcs1_der(bytes: &[u8]) -> Result<Self, SerializeError> {
+        match Der::from_der(bytes)?.as_sequence()? {
+            [n, e] => Ok(Self { n: from_der(n)?, e: from_der(e)? }),
+            _ => Err(malformed("RSAPublicKey { n, e }")),
+        }
+    }
+
+    pub fn to_pkcs1_pem(&self) -> String {
+        Pem::new("RSA PUBLIC KEY", self.to_pkcs1_der()).encode()
+    }
+
+    pub fn from_pkcs1_pem(text: &str) -> Result<Self, SerializeError> {
+        Self::from_pkcs1_der(&Pem::parse(text)?.expect("RSA PUBLIC KEY")?)
+    }
+
+    /// X.509 SubjectPublicKeyInfo around the PKCS#1 key, as `openssl rsa -pubout` writes
+    pub fn to_spki_der(&self) -> Vec<u8> {
+        SubjectPublicKeyInfo {
+            algorithm: RSA_ENCRYPTION.to_vec(),
+            parameters: Some(Der::Null),
+            public_key: self.to_pkcs1_der(),
+        }
+        .to_der()
+    }
+
+    pub fn from_spki_der(bytes: &[u8]) -> Result<Self, SerializeError> {
+        let info = SubjectPublicKeyInfo::from_der(bytes)?;
+        if info.algorithm != RSA_ENCRYPTION {
+            return Err(malformed("rsaEncryption key"));
+        }
+        Self::from_pkcs1_der(&info.public_key)
+    }
+
+    pub fn to_spki_pem(&self) -> String {
+        Pem::new("PUBLIC KEY", self.to_spki_der()).encode()
+    }
+
+    pub fn from_spki_pem(text: &str) -> Result<Self, SerializeError> {
+        Self::from_spki_der(&Pem::parse(text)?.expect("PUBLIC KEY")?)
+    }
+}
+
+impl RsaPrivateKey {
+    /// PKCS#1 RSAPrivateKey: version 0, n, e, d, p, q and the CRT values
+    /// d mod (p - 1), d mod (q - 1), q^-1 mod p
+    pub fn to_pkcs1_der(&self) -> Vec<u8> {
+        let q_inverse = modinv(&self.q, &self.p).unwrap_or_default();
+        let values = [
+            BigUint::default(),
+            self.n.clone(),
+            self.e.clone(),
+            self.d.clone(),
+            self.p.clone(),
+            self.q.clone(),
+            &self.d % (&self.p - 1u32),
+            &self.d % (&self.q - 1u32),
+            q_inverse,
+        ];
+
+        Der::Sequence(values.iter().map(to_der).collect()).to_der()
+    }
+
+    /// Reads the two-prime form; the CRT values are recomputed when needed,
+    /// so they aren't kept
+    pub fn from_pkcs1_der(bytes: &[u8]) -> Result<Self, SerializeError> {
+        match Der::from_der(bytes)?.as_sequence()? {
+            [version, n, e, d, p, q, _, _, _] if from_der(version)? == BigUint::default() => Ok(Self {
+                n: from_der(n)?,
+                e: from_der(e)?,
+                d: from_der(d)?,
+                p: from_der(p)?,
+                q: from_der(q)?,
+            }),
+            _ => Err(malformed("two-prime RSAPrivateKey")),
+        }
+    }
+
+    pub fn to_pkcs1_pem(&self) -> String {
+        Pem::new("RSA PRIVATE KEY", self.to_pkcs1_der()).encode()
+    }
+
+    pub fn from_pkcs1_pem(text: &str) -> Result<Self, SerializeError> {
+        Self::from_pkcs1_der(&Pem::parse(text)?.expect("RSA PRIVATE KEY")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rsa::RsaKeyPair;
+
+    fn fixture(name: &str) -> String {
+        let path = format!("{}/../data/keys/{}", env!("CARGO_MANIFEST_DIR"), name);
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_openssl_fixtures_round_trip() {
+        let private_pem = fixture("rsa1024.pem");
+        let private = RsaPrivateKey::from_pkcs1_pem(&private_pem).unwrap();
+        assert!(private.validate().is_ok());
+        assert_eq!(private.to_pkcs1_pem(), private_pem);
+
+        let public = private.public_key();
+        assert_eq!(public.to_pkcs1_pem(), fixture("rsa1024_pkcs1_pub.pem"));
+        assert_eq!(public.to_spki_pem(), fixture("rsa1024_spki.pem"));
+        assert_eq!(RsaPublicKey::from_spki_pem(&fixture("rsa1024_spki.pem")).unwrap(), public);
+    }
+
+    #[test]
+    fn test_generated_keys_round_trip() {
+        let keys = RsaKeyPair::generate(512, 65537);
+
+        assert_eq!(RsaPrivateKey::from_pkcs1_der(&keys.private.to_pkcs1_der()).unwrap(), keys.private);
+        assert_eq!(RsaPublicKey::from_pkcs1_pem(&keys.public.to_pkcs1_pem()).unwrap(), keys.public);
+        assert_eq!(RsaPublicKey::from_spki_der(&keys.public.to_spki_der()).unwrap(), keys.public);
+
+        assert!(RsaPublicKey::from_spki_der(&keys.public.to_pkcs1_der()).is_err());
+        assert!(RsaPublicKey::from_spki_pem(&keys.public.to_pkcs1_pem()).is_err());
+    }
+}
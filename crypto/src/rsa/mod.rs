@@ -0,0 +1,296 @@
+// crypto/src/rsa/mod.rs
+pub mod attacks;
+pub mod bleichenbacher;
+pub mod encoding;
+pub mod pkcs1;
+
+pub use attacks::{hastad_broadcast, parity_attack, unpadded_message_recovery, DecryptionOracle, ParityOracle};
+pub use bleichenbacher::{bleichenbacher98, forge_signature, PaddingOracle};
+pub use pkcs1::{pad_type1, pad_type2, unpad_type1, unpad_type2, DigestAlgorithm};
+
+use serialize::{from_base64, from_hex, to_base64, to_hex, Serialize, SerializeError};
+
+use crate::bigint::{mod_sub, modinv, random_prime, to_bytes_padded, BigUint, Integer, One};
+use crate::common::RsaError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RsaPublicKey {
+    pub n: BigUint,
+    pub e: BigUint,
+}
+
+/// Keeps p and q alongside d so the key can be exported in full
+#[derive(Debug, Clone, PartialEq)]
+pub struct RsaPrivateKey {
+    pub n: BigUint,
+    pub e: BigUint,
+    pub d: BigUint,
+    pub p: BigUint,
+    pub q: BigUint,
+}
+
+#[derive(Debug, Clone)]
+pub struct RsaKeyPair {
+    pub public: RsaPublicKey,
+    pub private: RsaPrivateKey,
+}
+
+impl RsaKeyPair {
+    /// Fresh key with a `bits`-bit modulus and public exponent `e`
+    pub fn generate(bits: u64, e: u64) -> Self {
+        assert!(bits >= 16, "RSA modulus too small");
+
+        let e = BigUint::from(e);
+        loop {
+            let p = random_prime(bits / 2);
+            let q = random_prime(bits - bits / 2);
+            if p == q {
+                continue;
+            }
+
+            let n = &p * &q;
+            if n.bits() != bits {
+                continue;
+            }
+
+            let totient = (&p - 1u32) * (&q - 1u32);
+            // e has to be invertible mod (p-1)(q-1), otherwise pick new primes
+            let Some(d) = modinv(&e, &totient) else {
+                continue;
+            };
+
+            return Self {
+                public: RsaPublicKey { n: n.clone(), e: e.clone() },
+                private: RsaPrivateKey { n, e, d, p, q },
+            };
+        }
+    }
+}
+
+impl RsaPublicKey {
+    /// Modulus length in bytes
+    pub fn size(&self) -> usize {
+        self.n.bits().div_ceil(8) as usize
+    }
+
+    /// c = m^e mod n
+    pub fn encrypt(&self, m: &BigUint) -> Result<BigUint, RsaError> {
+        if *m >= self.n {
+            return Err(RsaError::MessageTooLarge);
+        }
+        Ok(m.modpow(&self.e, &self.n))
+    }
+
+    /// Encrypt a byte string, returning a ciphertext as wide as the modulus
+    pub fn encrypt_bytes(&self, message: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let c = self.encrypt(&BigUint::from_bytes_be(message))?;
+        Ok(to_bytes_padded(&c, self.size()))
+    }
+
+    /// Raw signature check: s^e mod n == m
+    pub fn verify(&self, m: &BigUint, signature: &BigUint) -> bool {
+        *signature < self.n && signature.modpow(&self.e, &self.n) == *m
+    }
+
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.to_bytes())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, SerializeError> {
+        Self::from_bytes(&from_hex(hex)?)
+    }
+
+    pub fn to_base64(&self) -> String {
+        to_base64(&self.to_bytes())
+    }
+
+    pub fn from_base64(base64: &str) -> Result<Self, SerializeError> {
+        Self::from_bytes(&from_base64(base64)?)
+    }
+}
+
+impl RsaPrivateKey {
+    pub fn public_key(&self) -> RsaPublicKey {
+        RsaPublicKey { n: self.n.clone(), e: self.e.clone() }
+    }
+
+    /// Modulus length in bytes
+    pub fn size(&self) -> usize {
+        self.n.bits().div_ceil(8) as usize
+    }
+
+    /// m = c^d mod n, computed mod p and mod q and recombined (Garner's CRT)
+    pub fn decrypt(&self, c: &BigUint) -> Result<BigUint, RsaError> {
+        if *c >= self.n {
+            return Err(RsaError::MessageTooLarge);
+        }
+
+        let q_inverse = modinv(&self.q, &self.p).ok_or(RsaError::InvalidKey)?;
+        let m1 = c.modpow(&(&self.d % (&self.p - 1u32)), &self.p);
+        let m2 = c.modpow(&(&self.d % (&self.q - 1u32)), &self.q);
+        let h = (q_inverse * mod_sub(&m1, &m2, &self.p)) % &self.p;
+
+        Ok(m2 + h * &self.q)
+    }
+
+    /// Decrypt to the minimal big-endian encoding of m (leading zeros are lost)
+    pub fn decrypt_bytes(&self, ciphertext: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let m = self.decrypt(&BigUint::from_bytes_be(ciphertext))?;
+        Ok(m.to_bytes_be())
+    }
+
+    /// Raw signature s = m^d mod n
+    pub fn sign(&self, m: &BigUint) -> Result<BigUint, RsaError> {
+        self.decrypt(m)
+    }
+
+    /// Sanity check that the stored numbers form a working key. d only has
+    /// to invert e mod λ(n) = lcm(p-1, q-1), so both the φ(n) inverse this
+    /// crate generates and the smaller λ(n) one other tools use pass.
+    pub fn validate(&self) -> Result<(), RsaError> {
+        let lambda = (&self.p - 1u32).lcm(&(&self.q - 1u32));
+        if &self.p * &self.q != self.n || !((&self.e * &self.d) % &lambda).is_one() {
+            return Err(RsaError::InvalidKey);
+        }
+        if !self.e.gcd(&lambda).is_one() {
+            return Err(RsaError::InvalidKey);
+        }
+        Ok(())
+    }
+
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.to_bytes())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, SerializeError> {
+        Self::from_bytes(&from_hex(hex)?)
+    }
+
+    pub fn to_base64(&self) -> String {
+        to_base64(&self.to_bytes())
+    }
+
+    pub fn from_base64(base64: &str) -> Result<Self, SerializeError> {
+        Self::from_bytes(&from_base64(base64)?)
+    }
+}
+
+/// Keys serialize as a run of SSH-style integers: a 4-byte big-endian length
+/// followed by the big-endian magnitude, for (n, e) or (n, e, d, p, q).
+impl Serialize for RsaPublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        encode_integers(&[&self.n, &self.e])
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, SerializeError> {
+        match decode_integers(bytes)?.as_slice() {
+            [n, e] => Ok(Self { n: n.clone(), e: e.clone() }),
+            _ => Err(SerializeError::InvalidData("expected n, e".to_string())),
+        }
+    }
+}
+
+impl Serialize for RsaPrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        encode_integers(&[&self.n, &self.e, &self.d, &self.p, &self.q])
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, SerializeError> {
+        match decode_integers(bytes)?.as_slice() {
+            [n, e, d, p, q] => Ok(Self {
+                n: n.clone(),
+                e: e.clone(),
+                d: d.clone(),
+                p: p.clone(),
+                q: q.clone(),
+            }),
+            _ => Err(SerializeError::InvalidData("expected n, e, d, p, q".to_string())),
+        }
+    }
+}
+
+fn encode_integers(values: &[&BigUint]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    for value in values {
+        let magnitude = value.to_bytes_be();
+        bytes.extend_from_slice(&(magnitude.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&magnitude);
+    }
+
+    bytes
+}
+
+fn decode_integers(mut bytes: &[u8]) -> Result<Vec<BigUint>, SerializeError> {
+    let mut values = Vec::new();
+
+    while !bytes.is_empty() {
+        if bytes.len() < 4 {
+            return Err(SerializeError::InvalidLength);
+        }
+        let len = u32::from_be_bytes(bytes[..4].try_into().unwrap()) as usize;
+        bytes = &bytes[4..];
+
+        if bytes.len() < len {
+            return Err(SerializeError::InvalidLength);
+        }
+        values.push(BigUint::from_bytes_be(&bytes[..len]));
+        bytes = &bytes[len..];
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_sign_verify() {
+        let keys = RsaKeyPair::generate(512, 65537);
+        assert_eq!(keys.public.n.bits(), 512);
+        assert!(keys.private.validate().is_ok());
+
+        let m = BigUint::from_bytes_be(b"attack at dawn");
+        let c = keys.public.encrypt(&m).unwrap();
+        assert_eq!(keys.private.decrypt(&c).unwrap(), m);
+
+        let ciphertext = keys.public.encrypt_bytes(b"attack at dawn").unwrap();
+        assert_eq!(ciphertext.len(), 64);
+        assert_eq!(keys.private.decrypt_bytes(&ciphertext).unwrap(), b"attack at dawn");
+
+        let s = keys.private.sign(&m).unwrap();
+        assert!(keys.public.verify(&m, &s));
+        assert!(!keys.public.verify(&(m + 1u32), &s));
+
+        assert_eq!(keys.public.encrypt(&keys.public.n), Err(RsaError::MessageTooLarge));
+    }
+
+    #[test]
+    fn test_validate_accepts_carmichael_exponent() {
+        let keys = RsaKeyPair::generate(256, 65537);
+        let lambda = (&keys.private.p - 1u32).lcm(&(&keys.private.q - 1u32));
+
+        let mut private = keys.private.clone();
+        private.d = modinv(&private.e, &lambda).unwrap();
+        assert!(private.validate().is_ok());
+        let m = BigUint::from(42u32);
+        assert_eq!(private.decrypt(&keys.public.encrypt(&m).unwrap()).unwrap(), m);
+
+        private.d += 1u32;
+        assert_eq!(private.validate(), Err(RsaError::InvalidKey));
+    }
+
+    #[test]
+    fn test_key_export_round_trip() {
+        let keys = RsaKeyPair::generate(256, 3);
+
+        assert_eq!(RsaPublicKey::from_hex(&keys.public.to_hex()).unwrap(), keys.public);
+        assert_eq!(RsaPublicKey::from_base64(&keys.public.to_base64()).unwrap(), keys.public);
+        assert_eq!(RsaPrivateKey::from_hex(&keys.private.to_hex()).unwrap(), keys.private);
+        assert_eq!(RsaPrivateKey::from_base64(&keys.private.to_base64()).unwrap(), keys.private);
+
+        assert!(RsaPublicKey::from_hex(&keys.private.to_hex()).is_err());
+        assert!(RsaPrivateKey::from_hex("00000004ffff").is_err());
+    }
+}
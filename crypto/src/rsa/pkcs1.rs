@@ -0,0 +1,183 @@
+// crypto/src/rsa/pkcs1.rs
+use rand::{rng, Rng};
+
+use crate::bigint::{to_bytes_padded, BigUint};
+use crate::common::RsaError;
+
+pub use crate::hash::DigestAlgorithm;
+
+use super::{RsaPrivateKey, RsaPublicKey};
+
+/// Minimum number of padding bytes in either block type
+const MIN_PADDING: usize = 8;
+
+/// DER DigestInfo headers that precede the hash in a type 1 block
+const SHA1_DIGEST_INFO: &[u8] = &[
+    0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14,
+];
+const SHA256_DIGEST_INFO: &[u8] = &[
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01,
+    0x05, 0x00, 0x04, 0x20,
+];
+
+pub fn digest_info(alg: DigestAlgorithm) -> &'static [u8] {
+    match alg {
+        DigestAlgorithm::Sha1 => SHA1_DIGEST_INFO,
+        DigestAlgorithm::Sha256 => SHA256_DIGEST_INFO,
+    }
+}
+
+/// DigestInfo header followed by the hash of `message`
+pub fn encode_digest(alg: DigestAlgorithm, message: &[u8]) -> Vec<u8> {
+    let mut encoded = digest_info(alg).to_vec();
+    encoded.extend_from_slice(&alg.hash(message));
+    encoded
+}
+
+/// 00 01 FF..FF 00 data, `k` bytes long
+pub fn pad_type1(data: &[u8], k: usize) -> Result<Vec<u8>, RsaError> {
+    if data.len() + 3 + MIN_PADDING > k {
+        return Err(RsaError::MessageTooLarge);
+    }
+
+    let mut block = vec![0x00, 0x01];
+    block.resize(k - data.len() - 1, 0xff);
+    block.push(0x00);
+    block.extend_from_slice(data);
+
+    Ok(block)
+}
+
+/// 00 02 <random nonzero bytes> 00 data, `k` bytes long
+pub fn pad_type2(data: &[u8], k: usize) -> Result<Vec<u8>, RsaError> {
+    if data.len() + 3 + MIN_PADDING > k {
+        return Err(RsaError::MessageTooLarge);
+    }
+
+    let mut rng = rng();
+    let mut block = vec![0x00, 0x02];
+    while block.len() < k - data.len() - 1 {
+        block.push(rng.random_range(1..=255));
+    }
+    block.push(0x00);
+    block.extend_from_slice(data);
+
+    Ok(block)
+}
+
+pub fn unpad_type1(block: &[u8], k: usize) -> Result<Vec<u8>, RsaError> {
+    unpad(block, k, 0x01)
+}
+
+pub fn unpad_type2(block: &[u8], k: usize) -> Result<Vec<u8>, RsaError> {
+    unpad(block, k, 0x02)
+}
+
+fn unpad(block: &[u8], k: usize, block_type: u8) -> Result<Vec<u8>, RsaError> {
+    if block.len() != k || k < 3 + MIN_PADDING || block[0] != 0x00 || block[1] != block_type {
+        return Err(RsaError::InvalidPadding);
+    }
+
+    let separator = block[2..].iter()
+        .position(|&b| b == 0x00)
+        .ok_or(RsaError::InvalidPadding)? + 2;
+    let padding = &block[2..separator];
+
+    if padding.len() < MIN_PADDING || (block_type == 0x01 && padding.iter().any(|&b| b != 0xff)) {
+        return Err(RsaError::InvalidPadding);
+    }
+
+    Ok(block[separator + 1..].to_vec())
+}
+
+impl RsaPublicKey {
+    pub fn encrypt_pkcs1(&self, message: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let block = pad_type2(message, self.size())?;
+        self.encrypt_bytes(&block)
+    }
+
+    /// Rebuild the expected block and compare it in full
+    pub fn verify_pkcs1(&self, message: &[u8], signature: &[u8], alg: DigestAlgorithm) -> bool {
+        let Some(block) = self.signature_block(signature) else {
+            return false;
+        };
+
+        pad_type1(&encode_digest(alg, message), self.size()).is_ok_and(|expected| block == expected)
+    }
+
+    /// Parse the block left to right the way broken verifiers do: any run of
+    /// FF bytes, the DigestInfo, the hash, and whatever follows is ignored
+    pub fn verify_pkcs1_sloppy(&self, message: &[u8], signature: &[u8], alg: DigestAlgorithm) -> bool {
+        let Some(block) = self.signature_block(signature) else {
+            return false;
+        };
+
+        if block.len() < 3 || block[0] != 0x00 || block[1] != 0x01 || block[2] != 0xff {
+            return false;
+        }
+
+        let rest = &block[2..];
+        let padding_len = rest.iter().take_while(|&&b| b == 0xff).count();
+        let rest = &rest[padding_len..];
+
+        let expected = encode_digest(alg, message);
+        rest.first() == Some(&0x00) && rest[1..].starts_with(&expected)
+    }
+
+    fn signature_block(&self, signature: &[u8]) -> Option<Vec<u8>> {
+        let s = BigUint::from_bytes_be(signature);
+        if s >= self.n {
+            return None;
+        }
+        Some(to_bytes_padded(&s.modpow(&self.e, &self.n), self.size()))
+    }
+}
+
+impl RsaPrivateKey {
+    pub fn decrypt_pkcs1(&self, ciphertext: &[u8]) -> Result<Vec<u8>, RsaError> {
+        let m = self.decrypt(&BigUint::from_bytes_be(ciphertext))?;
+        unpad_type2(&to_bytes_padded(&m, self.size()), self.size())
+    }
+
+    pub fn sign_pkcs1(&self, message: &[u8], alg: DigestAlgorithm) -> Result<Vec<u8>, RsaError> {
+        let block = pad_type1(&encode_digest(alg, message), self.size())?;
+        let s = self.sign(&BigUint::from_bytes_be(&block))?;
+        Ok(to_bytes_padded(&s, self.size()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rsa::RsaKeyPair;
+
+    #[test]
+    fn test_padding_round_trip() {
+        let block = pad_type1(b"data", 32).unwrap();
+        assert_eq!(&block[..3], &[0x00, 0x01, 0xff]);
+        assert_eq!(unpad_type1(&block, 32).unwrap(), b"data");
+
+        let block = pad_type2(b"data", 32).unwrap();
+        assert_eq!(&block[..2], &[0x00, 0x02]);
+        assert!(block[2..27].iter().all(|&b| b != 0));
+        assert_eq!(unpad_type2(&block, 32).unwrap(), b"data");
+
+        assert_eq!(unpad_type1(&block, 32), Err(RsaError::InvalidPadding));
+        assert_eq!(pad_type2(&[0u8; 22], 32), Err(RsaError::MessageTooLarge));
+    }
+
+    #[test]
+    fn test_encrypt_and_sign() {
+        let keys = RsaKeyPair::generate(512, 65537);
+
+        let ciphertext = keys.public.encrypt_pkcs1(b"kick it, CC").unwrap();
+        assert_eq!(keys.private.decrypt_pkcs1(&ciphertext).unwrap(), b"kick it, CC");
+
+        for alg in [DigestAlgorithm::Sha1, DigestAlgorithm::Sha256] {
+            let signature = keys.private.sign_pkcs1(b"hi mom", alg).unwrap();
+            assert!(keys.public.verify_pkcs1(b"hi mom", &signature, alg));
+            assert!(keys.public.verify_pkcs1_sloppy(b"hi mom", &signature, alg));
+            assert!(!keys.public.verify_pkcs1(b"hi dad", &signature, alg));
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// crypto/src/srp/attacks.rs
+use crate::bigint::{BigUint, Zero};
+use crate::common::SrpError;
+
+use super::{proof, session_key, SrpHost, SrpMessage, SrpParams};
+
+/// Log in as `email` without the password by sending A = multiple * N.
+/// The host's secret (A * v^u)^b is then 0 mod N, so K = H(0).
+pub fn zero_key_login<H: SrpHost + ?Sized>(
+    host: &mut H,
+    params: &SrpParams,
+    email: &str,
+    multiple: u32,
+) -> Result<bool, SrpError> {
+    let public = &params.n * multiple;
+
+    let salt = match host.handle(SrpMessage::ClientHello { email: email.to_string(), public })? {
+        SrpMessage::ServerHello { salt, .. } => salt,
+        _ => return Err(SrpError::UnexpectedMessage),
+    };
+
+    let forged = proof(&session_key(&BigUint::zero()), &salt);
+    match host.handle(SrpMessage::Proof(forged))? {
+        SrpMessage::Result(ok) => Ok(ok),
+        _ => Err(SrpError::UnexpectedMessage),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::srp::SrpServer;
+
+    #[test]
+    fn test_zero_key_bypass() {
+        let params = SrpParams::nist();
+        let mut server = SrpServer::without_validation(params.clone());
+        server.register("alice@example.com", "a password nobody will guess");
+
+        for multiple in [0, 1, 2] {
+            assert_eq!(zero_key_login(&mut server, &params, "alice@example.com", multiple), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_validating_server_rejects_zero_key() {
+        let params = SrpParams::nist();
+        let mut server = SrpServer::new(params.clone());
+        server.register("alice@example.com", "a password nobody will guess");
+
+        for multiple in [0, 1, 2] {
+            assert_eq!(
+                zero_key_login(&mut server, &params, "alice@example.com", multiple),
+                Err(SrpError::InvalidPublicKey)
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+// crypto/src/srp/client.rs
+use crate::bigint::{mod_sub, random_range, BigUint, One, Zero};
+use crate::common::SrpError;
+
+use super::{private_key, proof, scrambler, session_key, SrpHost, SrpMessage, SrpParams};
+
+pub struct SrpClient {
+    params: SrpParams,
+    email: String,
+    password: String,
+    private: BigUint,
+    public: BigUint,
+}
+
+impl SrpClient {
+    pub fn new(params: SrpParams, email: &str, password: &str) -> Self {
+        let private = random_range(&BigUint::one(), &params.n);
+        let public = params.g.modpow(&private, &params.n);
+
+        Self {
+            params,
+            email: email.to_string(),
+            password: password.to_string(),
+            private,
+            public,
+        }
+    }
+
+    /// C -> S: I, A = g^a mod N
+    pub fn hello(&self) -> SrpMessage {
+        SrpMessage::ClientHello { email: self.email.clone(), public: self.public.clone() }
+    }
+
+    /// Turn the host's salt and B into the proof of K
+    pub fn respond(&self, message: SrpMessage) -> Result<SrpMessage, SrpError> {
+        let n = &self.params.n;
+
+        let (salt, secret) = match message {
+            SrpMessage::ServerHello { salt, public } => {
+                let u = scrambler(&self.public, &public, n);
+                if (&public % n).is_zero() || u.is_zero() {
+                    return Err(SrpError::InvalidPublicKey);
+                }
+
+                // S = (B - k * g^x)^(a + u * x) mod N
+                let x = private_key(&salt, &self.email, &self.password);
+                let masked = (&self.params.k * self.params.g.modpow(&x, n)) % n;
+                let base = mod_sub(&public, &masked, n);
+                (salt, base.modpow(&(&self.private + u * x), n))
+            }
+            SrpMessage::SimplifiedServerHello { salt, public, u } => {
+                // S = B^(a + u * x) mod N
+                let x = private_key(&salt, &self.email, &self.password);
+                (salt, public.modpow(&(&self.private + u * x), n))
+            }
+            _ => return Err(SrpError::UnexpectedMessage),
+        };
+
+        Ok(SrpMessage::Proof(proof(&session_key(&secret), &salt)))
+    }
+}
+
+/// Run a full login over the in-process transport
+pub fn login<H: SrpHost + ?Sized>(client: &mut SrpClient, host: &mut H) -> Result<bool, SrpError> {
+    let server_hello = host.handle(client.hello())?;
+    let client_proof = client.respond(server_hello)?;
+
+    match host.handle(client_proof)? {
+        SrpMessage::Result(ok) => Ok(ok),
+        _ => Err(SrpError::UnexpectedMessage),
+    }
+}
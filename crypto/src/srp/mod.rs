@@ -0,0 +1,128 @@
+// crypto/src/srp/mod.rs
+pub mod attacks;
+pub mod client;
+pub mod server;
+pub mod simplified;
+
+pub use attacks::zero_key_login;
+pub use client::{login, SrpClient};
+pub use server::SrpServer;
+pub use simplified::{load_wordlist, MaliciousSimplifiedServer, SimplifiedServer};
+
+use crate::bigint::{from_bytes, to_bytes, to_bytes_padded, BigUint};
+use crate::common::SrpError;
+use crate::dh::DhGroup;
+use crate::hash::{sha256, Sha256};
+use crate::hmac::hmac_sha256;
+use crate::utils::oracle::random_bytes;
+
+const SALT_SIZE: usize = 16;
+
+/// Group parameters plus the SRP-6a multiplier k = H(N | PAD(g))
+#[derive(Debug, Clone)]
+pub struct SrpParams {
+    pub n: BigUint,
+    pub g: BigUint,
+    pub k: BigUint,
+}
+
+impl SrpParams {
+    pub fn new(n: BigUint, g: BigUint) -> Self {
+        let k = hash_padded(&[&n, &g], &n);
+        Self { n, g, k }
+    }
+
+    /// SRP over the 1536-bit MODP group used for Diffie-Hellman
+    pub fn nist() -> Self {
+        let DhGroup { p, g } = DhGroup::nist();
+        Self::new(p, g)
+    }
+}
+
+/// What a host stores for each user instead of the password
+pub(crate) struct Record {
+    pub(crate) salt: Vec<u8>,
+    pub(crate) verifier: BigUint,
+}
+
+/// Everything that crosses the wire between client and host
+#[derive(Debug, Clone, PartialEq)]
+pub enum SrpMessage {
+    ClientHello { email: String, public: BigUint },
+    ServerHello { salt: Vec<u8>, public: BigUint },
+    /// Simplified SRP also sends the scrambler u instead of deriving it
+    SimplifiedServerHello { salt: Vec<u8>, public: BigUint, u: BigUint },
+    Proof(Vec<u8>),
+    Result(bool),
+}
+
+/// The server side of a login, one message in and one message out
+pub trait SrpHost {
+    fn handle(&mut self, message: SrpMessage) -> Result<SrpMessage, SrpError>;
+}
+
+/// Fresh salt and verifier v = g^x mod N for storing a password
+pub fn generate_verifier(params: &SrpParams, email: &str, password: &str) -> (Vec<u8>, BigUint) {
+    let salt = random_bytes(SALT_SIZE);
+    let x = private_key(&salt, email, password);
+    let verifier = params.g.modpow(&x, &params.n);
+    (salt, verifier)
+}
+
+/// x = H(salt | H(email ":" password)), as in RFC 5054
+pub(crate) fn private_key(salt: &[u8], email: &str, password: &str) -> BigUint {
+    let identity = sha256(format!("{}:{}", email, password).as_bytes());
+
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(&identity);
+    from_bytes(&hasher.finalize())
+}
+
+/// u = H(PAD(A) | PAD(B))
+pub(crate) fn scrambler(client_public: &BigUint, server_public: &BigUint, n: &BigUint) -> BigUint {
+    hash_padded(&[client_public, server_public], n)
+}
+
+/// K = H(S)
+pub(crate) fn session_key(secret: &BigUint) -> Vec<u8> {
+    sha256(&to_bytes(secret))
+}
+
+/// The client's proof of K: HMAC-SHA256(K, salt)
+pub(crate) fn proof(key: &[u8], salt: &[u8]) -> Vec<u8> {
+    hmac_sha256(key, salt)
+}
+
+/// Hash of the values left-padded to the length of N
+fn hash_padded(values: &[&BigUint], n: &BigUint) -> BigUint {
+    let width = to_bytes(n).len();
+    let mut hasher = Sha256::new();
+
+    for value in values {
+        hasher.update(&to_bytes_padded(value, width));
+    }
+
+    from_bytes(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_login_with_right_and_wrong_password() {
+        let params = SrpParams::nist();
+        let mut server = SrpServer::new(params.clone());
+        server.register("alice@example.com", "correct horse battery staple");
+
+        let mut client = SrpClient::new(params.clone(), "alice@example.com", "correct horse battery staple");
+        assert_eq!(login(&mut client, &mut server), Ok(true));
+
+        let mut client = SrpClient::new(params.clone(), "alice@example.com", "hunter2");
+        assert_eq!(login(&mut client, &mut server), Ok(false));
+
+        let mut client = SrpClient::new(params, "mallory@example.com", "hunter2");
+        assert_eq!(login(&mut client, &mut server), Err(SrpError::UnknownUser));
+    }
+}
@@ -0,0 +1,69 @@
+// crypto/src/srp/server.rs
+use std::collections::HashMap;
+
+use crate::bigint::{random_range, BigUint, One, Zero};
+use crate::common::SrpError;
+
+use super::{generate_verifier, proof, scrambler, session_key, Record, SrpHost, SrpMessage, SrpParams};
+
+pub struct SrpServer {
+    params: SrpParams,
+    users: HashMap<String, Record>,
+    expected_proof: Option<Vec<u8>>,
+    validate_public_keys: bool,
+}
+
+impl SrpServer {
+    pub fn new(params: SrpParams) -> Self {
+        Self {
+            params,
+            users: HashMap::new(),
+            expected_proof: None,
+            validate_public_keys: true,
+        }
+    }
+
+    /// A host that skips the A mod N != 0 check, as broken implementations do
+    pub fn without_validation(params: SrpParams) -> Self {
+        Self { validate_public_keys: false, ..Self::new(params) }
+    }
+
+    pub fn register(&mut self, email: &str, password: &str) {
+        let (salt, verifier) = generate_verifier(&self.params, email, password);
+        self.users.insert(email.to_string(), Record { salt, verifier });
+    }
+}
+
+impl SrpHost for SrpServer {
+    fn handle(&mut self, message: SrpMessage) -> Result<SrpMessage, SrpError> {
+        match message {
+            SrpMessage::ClientHello { email, public } => {
+                let n = &self.params.n;
+                let record = self.users.get(&email).ok_or(SrpError::UnknownUser)?;
+
+                if self.validate_public_keys && (&public % n).is_zero() {
+                    return Err(SrpError::InvalidPublicKey);
+                }
+
+                // B = k * v + g^b mod N
+                let b = random_range(&BigUint::one(), n);
+                let server_public = (&self.params.k * &record.verifier + self.params.g.modpow(&b, n)) % n;
+
+                // S = (A * v^u)^b mod N
+                let u = scrambler(&public, &server_public, n);
+                if u.is_zero() {
+                    return Err(SrpError::InvalidPublicKey);
+                }
+                let secret = (public * record.verifier.modpow(&u, n)).modpow(&b, n);
+                self.expected_proof = Some(proof(&session_key(&secret), &record.salt));
+
+                Ok(SrpMessage::ServerHello { salt: record.salt.clone(), public: server_public })
+            }
+            SrpMessage::Proof(mac) => {
+                let expected = self.expected_proof.take().ok_or(SrpError::NoSession)?;
+                Ok(SrpMessage::Result(mac == expected))
+            }
+            _ => Err(SrpError::UnexpectedMessage),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// crypto/src/srp/simplified.rs
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+use crate::bigint::{random_bits, random_range, BigUint, One};
+use crate::common::SrpError;
+
+use super::{generate_verifier, private_key, proof, session_key, Record, SrpHost, SrpMessage, SrpParams};
+
+/// Bits in the random scrambler u the simplified host sends
+const SCRAMBLER_BITS: u64 = 128;
+
+/// Simplified SRP: B = g^b and a random u, with no dependence on the verifier
+pub struct SimplifiedServer {
+    params: SrpParams,
+    users: HashMap<String, Record>,
+    expected_proof: Option<Vec<u8>>,
+}
+
+impl SimplifiedServer {
+    pub fn new(params: SrpParams) -> Self {
+        Self { params, users: HashMap::new(), expected_proof: None }
+    }
+
+    pub fn register(&mut self, email: &str, password: &str) {
+        let (salt, verifier) = generate_verifier(&self.params, email, password);
+        self.users.insert(email.to_string(), Record { salt, verifier });
+    }
+}
+
+impl SrpHost for SimplifiedServer {
+    fn handle(&mut self, message: SrpMessage) -> Result<SrpMessage, SrpError> {
+        match message {
+            SrpMessage::ClientHello { email, public } => {
+                let n = &self.params.n;
+                let record = self.users.get(&email).ok_or(SrpError::UnknownUser)?;
+
+                let b = random_range(&BigUint::one(), n);
+                let server_public = self.params.g.modpow(&b, n);
+                let u = random_bits(SCRAMBLER_BITS);
+
+                // S = (A * v^u)^b mod N
+                let secret = (public * record.verifier.modpow(&u, n)).modpow(&b, n);
+                self.expected_proof = Some(proof(&session_key(&secret), &record.salt));
+
+                Ok(SrpMessage::SimplifiedServerHello { salt: record.salt.clone(), public: server_public, u })
+            }
+            SrpMessage::Proof(mac) => {
+                let expected = self.expected_proof.take().ok_or(SrpError::NoSession)?;
+                Ok(SrpMessage::Result(mac == expected))
+            }
+            _ => Err(SrpError::UnexpectedMessage),
+        }
+    }
+}
+
+struct Capture {
+    email: String,
+    client_public: BigUint,
+    proof: Option<Vec<u8>>,
+}
+
+/// A host that picks b = 1, u = 1 and an empty salt, so the client's proof
+/// depends only on A and x: S = B^(a + x) = A * g^x mod N.
+pub struct MaliciousSimplifiedServer {
+    params: SrpParams,
+    capture: Option<Capture>,
+}
+
+impl MaliciousSimplifiedServer {
+    pub fn new(params: SrpParams) -> Self {
+        Self { params, capture: None }
+    }
+
+    /// Offline dictionary attack on the captured proof
+    pub fn crack<'a, I>(&self, words: I) -> Option<String>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let capture = self.capture.as_ref()?;
+        let captured_proof = capture.proof.as_ref()?;
+        let n = &self.params.n;
+
+        words.into_iter()
+            .find(|word| {
+                let x = private_key(&[], &capture.email, word);
+                let secret = (&capture.client_public * self.params.g.modpow(&x, n)) % n;
+                proof(&session_key(&secret), &[]) == *captured_proof
+            })
+            .map(str::to_string)
+    }
+}
+
+impl SrpHost for MaliciousSimplifiedServer {
+    fn handle(&mut self, message: SrpMessage) -> Result<SrpMessage, SrpError> {
+        match message {
+            SrpMessage::ClientHello { email, public } => {
+                self.capture = Some(Capture { email, client_public: public, proof: None });
+
+                Ok(SrpMessage::SimplifiedServerHello {
+                    salt: Vec::new(),
+                    public: self.params.g.clone(),
+                    u: BigUint::one(),
+                })
+            }
+            SrpMessage::Proof(mac) => {
+                let capture = self.capture.as_mut().ok_or(SrpError::NoSession)?;
+                capture.proof = Some(mac);
+
+                // Always "succeed"; the client has nothing to check this against
+                Ok(SrpMessage::Result(true))
+            }
+            _ => Err(SrpError::UnexpectedMessage),
+        }
+    }
+}
+
+/// One candidate password per non-empty line
+pub fn load_wordlist(path: &str) -> io::Result<Vec<String>> {
+    let content = fs::read_to_string(path)?;
+    Ok(content.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::srp::{login, SrpClient};
+    use rand::{rng, Rng};
+
+    fn wordlist() -> Vec<String> {
+        load_wordlist(concat!(env!("CARGO_MANIFEST_DIR"), "/../data/srp/wordlist.txt"))
+            .expect("Failed to read wordlist")
+    }
+
+    #[test]
+    fn test_simplified_login() {
+        let params = SrpParams::nist();
+        let mut server = SimplifiedServer::new(params.clone());
+        server.register("bob@example.com", "sunshine");
+
+        let mut client = SrpClient::new(params.clone(), "bob@example.com", "sunshine");
+        assert_eq!(login(&mut client, &mut server), Ok(true));
+
+        let mut client = SrpClient::new(params, "bob@example.com", "moonshine");
+        assert_eq!(login(&mut client, &mut server), Ok(false));
+    }
+
+    #[test]
+    fn test_offline_dictionary_attack() {
+        let words = wordlist();
+        let password = &words[rng().random_range(0..words.len())];
+
+        let params = SrpParams::nist();
+        let mut server = MaliciousSimplifiedServer::new(params.clone());
+        let mut client = SrpClient::new(params, "bob@example.com", password);
+        assert_eq!(login(&mut client, &mut server), Ok(true));
+
+        let cracked = server.crack(words.iter().map(String::as_str));
+        assert_eq!(cracked.as_ref(), Some(password));
+    }
+}
@@ -24,7 +24,7 @@ pub fn random_iv() -> [u8; 16] {
 
 pub fn random_bytes(len: usize) -> Vec<u8> {
     let mut rng = rng();
-    let mut bytes = Vec::with_capacity(len);
+    let mut bytes = vec![0u8; len];
 
     for byte in bytes.iter_mut() {
         *byte = rng.random();
@@ -37,4 +37,17 @@ pub fn random_padding(min: usize, max: usize) -> Vec<u8> {
     let mut rng = rng();
     let len = rng.random_range(min..=max);
     random_bytes(len)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_bytes_length() {
+        for len in [0, 1, 16, 33] {
+            assert_eq!(random_bytes(len).len(), len);
+        }
+        assert!((5..=10).contains(&random_padding(5, 10).len()));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::SerializeError;
+
+const CHARS: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// RFC 4648 section 6 Base32, padded to a multiple of 8 characters
+pub fn to_base32(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len().div_ceil(5) * 8);
+
+    for chunk in bytes.chunks(5) {
+        let mut block = [0u8; 8];
+        block[3..3 + chunk.len()].copy_from_slice(chunk);
+        let combined = u64::from_be_bytes(block);
+
+        // n bytes carry 8n bits, which take ceil(8n / 5) characters
+        let used = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < used {
+                result.push(CHARS[(combined >> (35 - 5 * i)) as usize & 0x1f] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+
+    result
+}
+
+/// Decodes Base32 the way authenticator apps get it: case-insensitive, with
+/// whitespace ignored and padding optional (but correct if present)
+pub fn from_base32(base32: &str) -> Result<Vec<u8>, SerializeError> {
+    let mut result = Vec::with_capacity(base32.len() * 5 / 8);
+    let mut buffer = 0u64;
+    let mut bits = 0;
+    let mut count = 0usize;
+    let mut padding = Vec::new();
+
+    for (offset, &c) in base32.as_bytes().iter().enumerate() {
+        if c.is_ascii_whitespace() {
+            continue;
+        }
+        if c == b'=' {
+            padding.push(offset);
+            continue;
+        }
+        if let Some(&first) = padding.first() {
+            return Err(SerializeError::InvalidPadding { offset: first });
+        }
+
+        let value = match c.to_ascii_uppercase() {
+            c @ b'A'..=b'Z' => c - b'A',
+            c @ b'2'..=b'7' => c - b'2' + 26,
+            _ => return Err(SerializeError::InvalidCharacter { offset, byte: c }),
+        };
+
+        buffer = buffer << 5 | value as u64;
+        bits += 5;
+        count += 1;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+        }
+    }
+
+    // Whole bytes end a group only after 2, 4, 5 or 7 characters
+    if matches!(count % 8, 1 | 3 | 6) {
+        return Err(SerializeError::TruncatedInput { offset: base32.len() });
+    }
+
+    let missing = (8 - count % 8) % 8;
+    if !padding.is_empty() && padding.len() != missing {
+        let offset = padding.get(missing).copied().unwrap_or(base32.len());
+        return Err(SerializeError::InvalidPadding { offset });
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc4648_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "MY======"),
+            ("fo", "MZXQ===="),
+            ("foo", "MZXW6==="),
+            ("foob", "MZXW6YQ="),
+            ("fooba", "MZXW6YTB"),
+            ("foobar", "MZXW6YTBOI======"),
+        ];
+
+        for (plain, encoded) in vectors {
+            assert_eq!(to_base32(plain.as_bytes()), encoded);
+            assert_eq!(from_base32(encoded).unwrap(), plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_totp_style_input() {
+        assert_eq!(from_base32("mzxw 6ytb oi").unwrap(), b"foobar");
+        assert_eq!(from_base32("MZXW6YQ").unwrap(), b"foob");
+        assert_eq!(from_base32("MZXW6Y"), Err(SerializeError::TruncatedInput { offset: 6 }));
+        assert_eq!(from_base32("MZXW1"), Err(SerializeError::InvalidCharacter { offset: 4, byte: b'1' }));
+        assert_eq!(from_base32("MY=====").unwrap_err(), SerializeError::InvalidPadding { offset: 7 });
+    }
+}
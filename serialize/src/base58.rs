@@ -0,0 +1,105 @@
+use crate::SerializeError;
+
+/// Bitcoin's alphabet: no 0, O, I or l
+const CHARS: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+const INVALID: u8 = 0xff;
+
+const DECODE: [u8; 128] = {
+    let mut table = [INVALID; 128];
+    let mut i = 0;
+    while i < 58 {
+        table[CHARS[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// The input as one big-endian number in base 58, with each leading zero
+/// byte kept as a leading `1`
+pub fn to_base58(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+
+    // Little-endian base-58 digits, multiplied by 256 and added into per byte
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &b in &bytes[zeros..] {
+        let mut carry = b as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    std::iter::repeat_n('1', zeros)
+        .chain(digits.iter().rev().map(|&d| CHARS[d as usize] as char))
+        .collect()
+}
+
+pub fn from_base58(base58: &str) -> Result<Vec<u8>, SerializeError> {
+    let input = base58.as_bytes();
+    let ones = input.iter().take_while(|&&c| c == b'1').count();
+
+    // Little-endian bytes, multiplied by 58 and added into per character
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len() * 733 / 1000 + 1);
+    for (offset, &c) in input.iter().enumerate().skip(ones) {
+        let value = DECODE.get(c as usize).copied().unwrap_or(INVALID);
+        if value == INVALID {
+            return Err(SerializeError::InvalidCharacter { offset, byte: c });
+        }
+
+        let mut carry = value as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut result = vec![0u8; ones];
+    result.extend(bytes.iter().rev());
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_hex;
+
+    #[test]
+    fn test_known_vectors() {
+        // From draft-msporny-base58
+        let vectors: [(&[u8], &str); 4] = [
+            (b"Hello World!", "2NEpo7TZRRrLZSi2U"),
+            (
+                b"The quick brown fox jumps over the lazy dog.",
+                "USm3fpXnKG5EUBx2ndxBDMPVciP5hGey2Jh4NDv6gmeo1LkMeiKrLJUUBk6Z",
+            ),
+            (&[0x00, 0x00, 0x28, 0x7f, 0xb4, 0xcd], "11233QC4"),
+            (b"", ""),
+        ];
+
+        for (plain, encoded) in vectors {
+            assert_eq!(to_base58(plain), encoded);
+            assert_eq!(from_base58(encoded).unwrap(), plain);
+        }
+    }
+
+    #[test]
+    fn test_bitcoin_address() {
+        // Version byte, RIPEMD-160 hash and checksum of the genesis block's address
+        let payload = from_hex("0062e907b15cbf27d5425399ebf6f0fb50ebb88f18c29b7d93").unwrap();
+        assert_eq!(to_base58(&payload), "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+        assert_eq!(from_base58("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa").unwrap(), payload);
+
+        assert_eq!(from_base58("1A0z"), Err(SerializeError::InvalidCharacter { offset: 2, byte: b'0' }));
+    }
+}
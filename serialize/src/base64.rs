@@ -0,0 +1,328 @@
+use crate::SerializeError;
+
+const STANDARD_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Which two characters encode 62 and 63
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alphabet {
+    /// RFC 4648 section 4: `+` and `/`
+    Standard,
+    /// RFC 4648 section 5: `-` and `_`, as in JWTs and cookies
+    UrlSafe,
+}
+
+impl Alphabet {
+    fn chars(self) -> &'static [u8; 64] {
+        match self {
+            Alphabet::Standard => STANDARD_CHARS,
+            Alphabet::UrlSafe => URL_SAFE_CHARS,
+        }
+    }
+
+    fn value(self, c: u8) -> Option<u8> {
+        match c {
+            b'A'..=b'Z' => Some(c - b'A'),
+            b'a'..=b'z' => Some(c - b'a' + 26),
+            b'0'..=b'9' => Some(c - b'0' + 52),
+            b'+' if self == Alphabet::Standard => Some(62),
+            b'/' if self == Alphabet::Standard => Some(63),
+            b'-' if self == Alphabet::UrlSafe => Some(62),
+            b'_' if self == Alphabet::UrlSafe => Some(63),
+            _ => None,
+        }
+    }
+}
+
+/// What to do with trailing `=`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// Written when encoding, and input must be a multiple of 4 characters
+    Required,
+    /// Written when encoding; when decoding, either none or exactly the right amount
+    Optional,
+    /// Never written, and any `=` is an error
+    Forbidden,
+}
+
+/// What to do with whitespace between characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Whitespace {
+    /// Skip it, for line-wrapped files
+    Ignore,
+    /// Treat it as any other bad character
+    Reject,
+}
+
+/// How to encode and decode Base64
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base64Config {
+    pub alphabet: Alphabet,
+    pub padding: Padding,
+    /// Reject encodings whose unused trailing bits aren't zero, so every
+    /// byte string has exactly one accepted encoding
+    pub strict: bool,
+    pub whitespace: Whitespace,
+}
+
+impl Base64Config {
+    /// Padded standard alphabet, tolerant of line breaks and stray trailing bits
+    pub const STANDARD: Self = Self {
+        alphabet: Alphabet::Standard,
+        padding: Padding::Required,
+        strict: false,
+        whitespace: Whitespace::Ignore,
+    };
+
+    /// Padded URL-safe alphabet
+    pub const URL_SAFE: Self = Self {
+        alphabet: Alphabet::UrlSafe,
+        padding: Padding::Required,
+        strict: true,
+        whitespace: Whitespace::Reject,
+    };
+
+    /// Unpadded URL-safe alphabet, as in JWT segments (RFC 7515, section 2)
+    pub const URL_SAFE_NO_PAD: Self = Self {
+        alphabet: Alphabet::UrlSafe,
+        padding: Padding::Forbidden,
+        strict: true,
+        whitespace: Whitespace::Reject,
+    };
+
+    pub fn with_alphabet(self, alphabet: Alphabet) -> Self {
+        Self { alphabet, ..self }
+    }
+
+    pub fn with_padding(self, padding: Padding) -> Self {
+        Self { padding, ..self }
+    }
+
+    pub fn with_strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    pub fn with_whitespace(self, whitespace: Whitespace) -> Self {
+        Self { whitespace, ..self }
+    }
+}
+
+impl Default for Base64Config {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+pub fn to_base64(bytes: &[u8]) -> String {
+    to_base64_with(bytes, &Base64Config::STANDARD)
+}
+
+pub fn to_base64_with(bytes: &[u8], config: &Base64Config) -> String {
+    let chars = config.alphabet.chars();
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let mut block = [0u8; 3];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let combined = (block[0] as u32) << 16 | (block[1] as u32) << 8 | block[2] as u32;
+
+        // n bytes carry 8n bits, which take n + 1 characters
+        for i in 0..=chunk.len() {
+            let index = (combined >> (18 - 6 * i)) & 0x3F;
+            result.push(chars[index as usize] as char);
+        }
+
+        if config.padding != Padding::Forbidden {
+            for _ in chunk.len()..3 {
+                result.push('=');
+            }
+        }
+    }
+
+    result
+}
+
+pub fn from_base64(base64: &str) -> Result<Vec<u8>, SerializeError> {
+    from_base64_with(base64, &Base64Config::STANDARD)
+}
+
+/// Decodes under `config`. Errors point at the byte offset in `base64` of
+/// the offending character, or of the end of input when something is missing.
+pub fn from_base64_with(base64: &str, config: &Base64Config) -> Result<Vec<u8>, SerializeError> {
+    let mut result = Vec::with_capacity(base64.len() / 4 * 3);
+    let mut decoder = Decoder::new(*config);
+    decoder.feed(base64.as_bytes(), &mut result)?;
+    decoder.finish(&mut result)?;
+    Ok(result)
+}
+
+/// Decoding state that can be fed input in pieces split anywhere, for the
+/// streaming reader. Offsets in errors count from the start of all input.
+pub(crate) struct Decoder {
+    config: Base64Config,
+    accumulator: u32,
+    count: usize,
+    // Offset of the most recent character, for the trailing-bits check
+    last: usize,
+    padding: Vec<usize>,
+    offset: usize,
+}
+
+impl Decoder {
+    pub(crate) fn new(config: Base64Config) -> Self {
+        Self { config, accumulator: 0, count: 0, last: 0, padding: Vec::new(), offset: 0 }
+    }
+
+    pub(crate) fn feed(&mut self, input: &[u8], out: &mut Vec<u8>) -> Result<(), SerializeError> {
+        let config = self.config;
+
+        for &c in input {
+            let offset = self.offset;
+            self.offset += 1;
+
+            if c.is_ascii_whitespace() && config.whitespace == Whitespace::Ignore {
+                continue;
+            }
+
+            if c == b'=' {
+                if config.padding == Padding::Forbidden || self.count % 4 < 2 {
+                    return Err(SerializeError::InvalidPadding { offset });
+                }
+                self.padding.push(offset);
+                continue;
+            }
+
+            // Nothing but padding may follow padding
+            if let Some(&first) = self.padding.first() {
+                return Err(SerializeError::InvalidPadding { offset: first });
+            }
+
+            let value = config.alphabet.value(c)
+                .ok_or(SerializeError::InvalidCharacter { offset, byte: c })?;
+
+            self.accumulator = self.accumulator << 6 | value as u32;
+            self.count += 1;
+            self.last = offset;
+
+            if self.count.is_multiple_of(4) {
+                out.extend_from_slice(&self.accumulator.to_be_bytes()[1..]);
+                self.accumulator = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the final partial group and checks the padding
+    pub(crate) fn finish(&self, out: &mut Vec<u8>) -> Result<(), SerializeError> {
+        let missing = (4 - self.count % 4) % 4;
+        match self.count % 4 {
+            0 => {}
+            1 => return Err(SerializeError::TruncatedInput { offset: self.offset }),
+            _ => {
+                // 2 characters carry one byte and 4 spare bits, 3 carry two bytes and 2
+                let spare = 2 * missing as u32;
+                if self.config.strict && self.accumulator & ((1 << spare) - 1) != 0 {
+                    return Err(SerializeError::NonCanonical { offset: self.last });
+                }
+
+                let bits = self.accumulator >> spare;
+                let bytes = (3 - missing) as u32;
+                for i in (0..bytes).rev() {
+                    out.push((bits >> (8 * i)) as u8);
+                }
+            }
+        }
+
+        let padded = match self.config.padding {
+            Padding::Required => self.padding.len() == missing,
+            Padding::Optional => self.padding.is_empty() || self.padding.len() == missing,
+            Padding::Forbidden => self.padding.is_empty(),
+        };
+        if !padded {
+            let offset = self.padding.get(missing).copied().unwrap_or(self.offset);
+            return Err(SerializeError::InvalidPadding { offset });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_round_trip() {
+        for config in [Base64Config::STANDARD, Base64Config::URL_SAFE, Base64Config::URL_SAFE_NO_PAD] {
+            for len in 0..8 {
+                let bytes: Vec<u8> = (0..len).map(|i| 0xfb ^ (i * 37)).collect();
+                assert_eq!(from_base64_with(&to_base64_with(&bytes, &config), &config).unwrap(), bytes);
+            }
+        }
+
+        assert_eq!(to_base64(b"\xfb\xff"), "+/8=");
+        assert_eq!(to_base64_with(b"\xfb\xff", &Base64Config::URL_SAFE_NO_PAD), "-_8");
+    }
+
+    #[test]
+    fn test_jwt_segment() {
+        let header = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9";
+        let decoded = from_base64_with(header, &Base64Config::URL_SAFE_NO_PAD).unwrap();
+        assert_eq!(decoded, br#"{"alg":"HS256","typ":"JWT"}"#);
+
+        assert_eq!(
+            from_base64_with("eyJ9=", &Base64Config::URL_SAFE_NO_PAD),
+            Err(SerializeError::InvalidPadding { offset: 4 })
+        );
+        assert_eq!(
+            from_base64_with("ab+c", &Base64Config::URL_SAFE_NO_PAD),
+            Err(SerializeError::InvalidCharacter { offset: 2, byte: b'+' })
+        );
+    }
+
+    #[test]
+    fn test_errors_carry_offsets() {
+        let standard = Base64Config::STANDARD;
+        assert_eq!(from_base64("QU=JD"), Err(SerializeError::InvalidPadding { offset: 2 }));
+        assert_eq!(from_base64("QUJD\nQU!D"), Err(SerializeError::InvalidCharacter { offset: 7, byte: b'!' }));
+        assert_eq!(from_base64("QUI"), Err(SerializeError::InvalidPadding { offset: 3 }));
+        assert_eq!(from_base64("QUJDR"), Err(SerializeError::TruncatedInput { offset: 5 }));
+        assert_eq!(from_base64("QUI=="), Err(SerializeError::InvalidPadding { offset: 4 }));
+
+        let optional = standard.with_padding(Padding::Optional);
+        assert_eq!(from_base64_with("QUI", &optional).unwrap(), b"AB");
+        assert_eq!(from_base64_with("QUI=", &optional).unwrap(), b"AB");
+
+        let rejecting = standard.with_whitespace(Whitespace::Reject);
+        assert_eq!(from_base64_with("QU JD", &rejecting), Err(SerializeError::InvalidCharacter { offset: 2, byte: b' ' }));
+    }
+
+    #[test]
+    fn test_strict_trailing_bits() {
+        // "QUJ=" and "QUI=" both decode to "AB" leniently; only the latter is canonical
+        assert_eq!(from_base64("QUJ=").unwrap(), b"AB");
+        let strict = Base64Config::STANDARD.with_strict(true);
+        assert_eq!(from_base64_with("QUJ=", &strict), Err(SerializeError::NonCanonical { offset: 2 }));
+        assert_eq!(from_base64_with("QUI=", &strict).unwrap(), b"AB");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_round_trip(bytes in prop::collection::vec(any::<u8>(), 0..256)) {
+            for config in [Base64Config::STANDARD, Base64Config::URL_SAFE, Base64Config::URL_SAFE_NO_PAD] {
+                prop_assert_eq!(from_base64_with(&to_base64_with(&bytes, &config), &config).unwrap(), bytes.as_slice());
+            }
+        }
+
+        /// Strict decoding accepts exactly one spelling of each byte string
+        #[test]
+        fn prop_strict_decode_is_canonical(text in "[A-Za-z0-9+/]{0,12}={0,2}") {
+            let strict = Base64Config::STANDARD.with_strict(true);
+            if let Ok(bytes) = from_base64_with(&text, &strict) {
+                prop_assert_eq!(to_base64(&bytes), text);
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::SerializeError;
+
+/// Ascii85 as in PostScript and PDF (`btoa` style): four bytes to five
+/// characters `!`..`u`, with `z` for a group of zeros. No `<~ ~>` delimiters
+/// are written.
+pub fn to_base85(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len().div_ceil(4) * 5);
+
+    for chunk in bytes.chunks(4) {
+        let mut block = [0u8; 4];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(block);
+
+        if chunk.len() == 4 && value == 0 {
+            result.push('z');
+            continue;
+        }
+
+        let mut digits = [0u8; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = (value % 85) as u8 + b'!';
+            value /= 85;
+        }
+        // A partial group of n bytes keeps its first n + 1 characters
+        result.extend(digits[..chunk.len() + 1].iter().map(|&d| d as char));
+    }
+
+    result
+}
+
+/// Accepts the optional `<~ ~>` delimiters and any whitespace
+pub fn from_base85(base85: &str) -> Result<Vec<u8>, SerializeError> {
+    let mut input = base85.as_bytes();
+    let mut start = 0;
+    if input.starts_with(b"<~") {
+        input = &input[2..];
+        start = 2;
+    }
+    if input.ends_with(b"~>") {
+        input = &input[..input.len() - 2];
+    }
+
+    let mut result = Vec::with_capacity(input.len() * 4 / 5);
+    let mut group = [0u8; 5];
+    let mut count = 0;
+    // Offset of the group's first character, for overflow errors
+    let mut group_start = start;
+
+    for (i, &c) in input.iter().enumerate() {
+        let offset = start + i;
+        match c {
+            c if c.is_ascii_whitespace() => {}
+            b'z' if count == 0 => result.extend_from_slice(&[0; 4]),
+            b'!'..=b'u' => {
+                if count == 0 {
+                    group_start = offset;
+                }
+                group[count] = c - b'!';
+                count += 1;
+                if count == 5 {
+                    result.extend_from_slice(&decode_group(&group, group_start)?);
+                    count = 0;
+                }
+            }
+            _ => return Err(SerializeError::InvalidCharacter { offset, byte: c }),
+        }
+    }
+
+    match count {
+        0 => {}
+        1 => return Err(SerializeError::TruncatedInput { offset: base85.len() }),
+        _ => {
+            // Pad with the highest digit so the kept bytes round up correctly
+            group[count..].fill(84);
+            result.extend_from_slice(&decode_group(&group, group_start)?[..count - 1]);
+        }
+    }
+
+    Ok(result)
+}
+
+fn decode_group(group: &[u8; 5], offset: usize) -> Result<[u8; 4], SerializeError> {
+    let value = group.iter().fold(0u64, |value, &d| value * 85 + d as u64);
+    u32::try_from(value)
+        .map(u32::to_be_bytes)
+        .map_err(|_| SerializeError::InvalidData(format!("Ascii85 group at offset {} exceeds 32 bits", offset)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_vectors() {
+        let vectors: [(&[u8], &str); 6] = [
+            (b"Man ", "9jqo^"),
+            (b"sure.", "F*2M7/c"),
+            (b"Man is distinguished", "9jqo^BlbD-BleB1DJ+*+F(f,q"),
+            (&[0, 0, 0, 0], "z"),
+            (&[0, 0, 0, 0, 1], "z!<"),
+            (&[0xff; 4], "s8W-!"),
+        ];
+
+        for (plain, encoded) in vectors {
+            assert_eq!(to_base85(plain), encoded);
+            assert_eq!(from_base85(encoded).unwrap(), plain);
+        }
+    }
+
+    #[test]
+    fn test_pdf_stream_input() {
+        assert_eq!(from_base85("<~9jqo^Blb\nD-BleB1DJ+*+F(f,q~>").unwrap(), b"Man is distinguished");
+        assert_eq!(from_base85("9jzqo"), Err(SerializeError::InvalidCharacter { offset: 2, byte: b'z' }));
+        assert!(from_base85("s8W-\"").is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{
+    from_base32, from_base58, from_base64_with, from_base85, from_hex_with, percent_decode, percent_encode,
+    to_base32, to_base58, to_base64_with, to_base85, to_hex, Base64Config, HexConfig, SerializeError,
+};
+
+/// A reversible text encoding of bytes
+pub trait Codec {
+    fn encode(&self, bytes: &[u8]) -> String;
+    fn decode(&self, text: &str) -> Result<Vec<u8>, SerializeError>;
+}
+
+/// Every encoding the crate knows, by name, for picking an input or output
+/// format from a command line or a test-vector file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// The text's own UTF-8 bytes
+    Raw,
+    Hex,
+    Base64,
+    /// URL-safe alphabet without padding, as in JWTs
+    Base64Url,
+    Base32,
+    Base58,
+    /// Ascii85
+    Base85,
+    Percent,
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 8] = [
+        Encoding::Raw,
+        Encoding::Hex,
+        Encoding::Base64,
+        Encoding::Base64Url,
+        Encoding::Base32,
+        Encoding::Base58,
+        Encoding::Base85,
+        Encoding::Percent,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Raw => "raw",
+            Encoding::Hex => "hex",
+            Encoding::Base64 => "base64",
+            Encoding::Base64Url => "base64url",
+            Encoding::Base32 => "base32",
+            Encoding::Base58 => "base58",
+            Encoding::Base85 => "base85",
+            Encoding::Percent => "percent",
+        }
+    }
+}
+
+impl Codec for Encoding {
+    fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Raw => String::from_utf8_lossy(bytes).into_owned(),
+            Encoding::Hex => to_hex(bytes),
+            Encoding::Base64 => to_base64_with(bytes, &Base64Config::STANDARD),
+            Encoding::Base64Url => to_base64_with(bytes, &Base64Config::URL_SAFE_NO_PAD),
+            Encoding::Base32 => to_base32(bytes),
+            Encoding::Base58 => to_base58(bytes),
+            Encoding::Base85 => to_base85(bytes),
+            Encoding::Percent => percent_encode(bytes),
+        }
+    }
+
+    /// Hex and Base64 are decoded as leniently as their configs allow, since
+    /// input usually comes from a terminal or a file
+    fn decode(&self, text: &str) -> Result<Vec<u8>, SerializeError> {
+        match self {
+            Encoding::Raw => Ok(text.as_bytes().to_vec()),
+            Encoding::Hex => from_hex_with(text.trim().as_bytes(), &HexConfig::LENIENT),
+            Encoding::Base64 => from_base64_with(text, &Base64Config::STANDARD),
+            Encoding::Base64Url => from_base64_with(text.trim(), &Base64Config::URL_SAFE_NO_PAD),
+            Encoding::Base32 => from_base32(text),
+            Encoding::Base58 => from_base58(text.trim()),
+            Encoding::Base85 => from_base85(text),
+            Encoding::Percent => percent_decode(text),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = SerializeError;
+
+    /// Case-insensitive, with a few common aliases
+    fn from_str(s: &str) -> Result<Self, SerializeError> {
+        let name = s.to_ascii_lowercase();
+        let alias = match name.as_str() {
+            "b64" => "base64",
+            "b64url" | "base64-url" | "jwt" => "base64url",
+            "b32" => "base32",
+            "b58" => "base58",
+            "ascii85" | "a85" => "base85",
+            "url" | "urlencoded" => "percent",
+            "utf8" | "text" => "raw",
+            other => other,
+        };
+
+        Encoding::ALL.into_iter()
+            .find(|encoding| encoding.name() == alias)
+            .ok_or_else(|| SerializeError::InvalidData(format!("unknown encoding: {}", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_encoding_round_trips() {
+        let bytes = b"\x00\x01 YELLOW SUBMARINE \xfe\xff".to_vec();
+
+        for encoding in Encoding::ALL.into_iter().filter(|&e| e != Encoding::Raw) {
+            let text = encoding.encode(&bytes);
+            assert_eq!(encoding.decode(&text).unwrap(), bytes, "{}", encoding);
+            assert_eq!(encoding.name().parse::<Encoding>().unwrap(), encoding);
+        }
+    }
+
+    #[test]
+    fn test_lookup_by_name() {
+        assert_eq!("ASCII85".parse::<Encoding>().unwrap(), Encoding::Base85);
+        assert_eq!("jwt".parse::<Encoding>().unwrap(), Encoding::Base64Url);
+        assert!("rot13".parse::<Encoding>().is_err());
+
+        let codec: Box<dyn Codec> = Box::new("hex".parse::<Encoding>().unwrap());
+        assert_eq!(codec.decode("0x4142").unwrap(), b"AB");
+    }
+}
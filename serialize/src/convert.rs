@@ -0,0 +1,148 @@
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::{from_base64, from_hex, to_base64, to_hex, FromBase64, FromHex, Serialize, SerializeError, ToBase64, ToHex};
+
+impl Serialize for Vec<u8> {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.clone()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, SerializeError> {
+        Ok(bytes.to_vec())
+    }
+}
+
+impl<const N: usize> Serialize for [u8; N] {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, SerializeError> {
+        bytes.try_into().map_err(|_| SerializeError::InvalidLength)
+    }
+}
+
+impl Serialize for String {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, SerializeError> {
+        String::from_utf8(bytes.to_vec()).map_err(|e| SerializeError::InvalidData(e.to_string()))
+    }
+}
+
+impl FromHex for str {
+    fn from_hex(&self) -> Result<Vec<u8>, SerializeError> {
+        from_hex(self)
+    }
+}
+
+impl FromHex for String {
+    fn from_hex(&self) -> Result<Vec<u8>, SerializeError> {
+        from_hex(self)
+    }
+}
+
+impl FromBase64 for str {
+    fn from_base64(&self) -> Result<Vec<u8>, SerializeError> {
+        from_base64(self)
+    }
+}
+
+impl FromBase64 for String {
+    fn from_base64(&self) -> Result<Vec<u8>, SerializeError> {
+        from_base64(self)
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> ToHex for T {
+    fn to_hex(&self) -> String {
+        to_hex(self.as_ref())
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> ToBase64 for T {
+    fn to_base64(&self) -> String {
+        to_base64(self.as_ref())
+    }
+}
+
+/// Bytes that display and parse as hex, e.g. `"00ff".parse::<Hex<[u8; 2]>>()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Hex<T = Vec<u8>>(pub T);
+
+/// Bytes that display and parse as standard padded Base64
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Base64<T = Vec<u8>>(pub T);
+
+macro_rules! newtype {
+    ($name:ident, $encode:ident, $decode:ident) => {
+        impl<T> $name<T> {
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T> Deref for $name<T> {
+            type Target = T;
+
+            fn deref(&self) -> &T {
+                &self.0
+            }
+        }
+
+        impl<T> From<T> for $name<T> {
+            fn from(value: T) -> Self {
+                Self(value)
+            }
+        }
+
+        impl<T: AsRef<[u8]>> fmt::Display for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&$encode(self.0.as_ref()))
+            }
+        }
+
+        impl<T: Serialize> FromStr for $name<T> {
+            type Err = SerializeError;
+
+            fn from_str(s: &str) -> Result<Self, SerializeError> {
+                T::from_bytes(&$decode(s)?).map(Self)
+            }
+        }
+    };
+}
+
+newtype!(Hex, to_hex, from_hex);
+newtype!(Base64, to_base64, from_base64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_traits() {
+        assert_eq!(vec![0xde, 0xad].to_hex(), "dead");
+        assert_eq!([0xbe, 0xef].to_hex(), "beef");
+        assert_eq!("hi".to_base64(), "aGk=");
+        assert_eq!("aGk=".from_base64().unwrap(), b"hi");
+        assert_eq!(String::from("cafe").from_hex().unwrap(), [0xca, 0xfe]);
+        assert!("caf".from_hex().is_err());
+    }
+
+    #[test]
+    fn test_newtypes() {
+        let key: Hex<[u8; 4]> = "00112233".parse().unwrap();
+        assert_eq!(*key, [0x00, 0x11, 0x22, 0x33]);
+        assert_eq!(key.to_string(), "00112233");
+        assert_eq!("0011".parse::<Hex<[u8; 4]>>(), Err(SerializeError::InvalidLength));
+
+        let text: Base64<String> = "aGVsbG8=".parse().unwrap();
+        assert_eq!(text.as_str(), "hello");
+        assert_eq!(Base64(b"hello").to_string(), "aGVsbG8=");
+        assert!("/w==".parse::<Base64<String>>().is_err());
+    }
+}
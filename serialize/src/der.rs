@@ -0,0 +1,325 @@
+use crate::SerializeError;
+
+pub const INTEGER: u8 = 0x02;
+pub const BIT_STRING: u8 = 0x03;
+pub const OCTET_STRING: u8 = 0x04;
+pub const NULL: u8 = 0x05;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const SEQUENCE: u8 = 0x30;
+
+/// Nesting deeper than this is refused rather than recursed into
+const MAX_DEPTH: usize = 32;
+
+/// The handful of ASN.1 types that key files are made of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Der {
+    /// Big-endian two's complement, minimal as DER requires
+    Integer(Vec<u8>),
+    /// Whole bytes only: every key format leaves no unused bits
+    BitString(Vec<u8>),
+    OctetString(Vec<u8>),
+    Null,
+    /// Arcs of an object identifier, e.g. [1, 2, 840, 113549, 1, 1, 1]
+    Oid(Vec<u64>),
+    Sequence(Vec<Der>),
+}
+
+fn invalid(offset: usize, reason: &'static str) -> SerializeError {
+    SerializeError::InvalidDer { offset, reason }
+}
+
+impl Der {
+    /// A non-negative INTEGER from a big-endian magnitude
+    pub fn unsigned(magnitude: &[u8]) -> Self {
+        let start = magnitude.iter().position(|&b| b != 0).unwrap_or(magnitude.len());
+        let mut content = magnitude[start..].to_vec();
+        // A set top bit would read as negative
+        if content.first().is_none_or(|&b| b & 0x80 != 0) {
+            content.insert(0, 0);
+        }
+        Der::Integer(content)
+    }
+
+    /// The big-endian magnitude of a non-negative INTEGER
+    pub fn as_unsigned(&self) -> Result<&[u8], SerializeError> {
+        match self {
+            Der::Integer(content) if content[0] & 0x80 == 0 => {
+                Ok(if content[0] == 0 { &content[1..] } else { content })
+            }
+            Der::Integer(_) => Err(invalid(0, "negative integer")),
+            _ => Err(invalid(0, "expected INTEGER")),
+        }
+    }
+
+    pub fn as_sequence(&self) -> Result<&[Der], SerializeError> {
+        match self {
+            Der::Sequence(items) => Ok(items),
+            _ => Err(invalid(0, "expected SEQUENCE")),
+        }
+    }
+
+    pub fn as_bit_string(&self) -> Result<&[u8], SerializeError> {
+        match self {
+            Der::BitString(bytes) => Ok(bytes),
+            _ => Err(invalid(0, "expected BIT STRING")),
+        }
+    }
+
+    pub fn as_oid(&self) -> Result<&[u64], SerializeError> {
+        match self {
+            Der::Oid(arcs) => Ok(arcs),
+            _ => Err(invalid(0, "expected OBJECT IDENTIFIER")),
+        }
+    }
+
+    pub fn to_der(&self) -> Vec<u8> {
+        let (tag, content) = match self {
+            Der::Integer(content) => (INTEGER, content.clone()),
+            Der::BitString(bytes) => (BIT_STRING, [&[0], bytes.as_slice()].concat()),
+            Der::OctetString(bytes) => (OCTET_STRING, bytes.clone()),
+            Der::Null => (NULL, Vec::new()),
+            Der::Oid(arcs) => (OBJECT_IDENTIFIER, encode_oid(arcs)),
+            Der::Sequence(items) => (SEQUENCE, items.iter().flat_map(Der::to_der).collect()),
+        };
+
+        let mut result = vec![tag];
+        encode_length(content.len(), &mut result);
+        result.extend_from_slice(&content);
+        result
+    }
+
+    /// Parses exactly one element. Only the minimal encodings DER allows are
+    /// accepted, and errors carry the offset in `bytes` where parsing stopped.
+    pub fn from_der(bytes: &[u8]) -> Result<Self, SerializeError> {
+        let (der, used) = parse(bytes, 0, 0)?;
+        if used != bytes.len() {
+            return Err(invalid(used, "trailing data"));
+        }
+        Ok(der)
+    }
+}
+
+fn encode_length(len: usize, out: &mut Vec<u8>) {
+    if len < 0x80 {
+        out.push(len as u8);
+        return;
+    }
+
+    let bytes = len.to_be_bytes();
+    let start = bytes.iter().position(|&b| b != 0).unwrap();
+    out.push(0x80 | (bytes.len() - start) as u8);
+    out.extend_from_slice(&bytes[start..]);
+}
+
+fn encode_oid(arcs: &[u64]) -> Vec<u8> {
+    assert!(arcs.len() >= 2 && arcs[0] <= 2, "invalid object identifier");
+
+    let mut result = Vec::new();
+    let first = arcs[0] * 40 + arcs[1];
+    for &arc in std::iter::once(&first).chain(&arcs[2..]) {
+        // Base 128, most significant group first, high bit set on all but the last
+        let groups = (64 - arc.leading_zeros()).div_ceil(7).max(1);
+        for i in (0..groups).rev() {
+            let group = (arc >> (7 * i)) as u8 & 0x7f;
+            result.push(if i == 0 { group } else { group | 0x80 });
+        }
+    }
+    result
+}
+
+/// Parses one element at `bytes[0]`, which sits at `base` in the whole input.
+/// Returns it and how many bytes it took.
+fn parse(bytes: &[u8], base: usize, depth: usize) -> Result<(Der, usize), SerializeError> {
+    if depth > MAX_DEPTH {
+        return Err(invalid(base, "nested too deeply"));
+    }
+
+    let &tag = bytes.first().ok_or(invalid(base, "missing tag"))?;
+    let (len, header) = parse_length(&bytes[1..], base + 1)?;
+    let header = header + 1;
+    let content = header.checked_add(len)
+        .and_then(|end| bytes.get(header..end))
+        .ok_or(invalid(base + 1, "length past end of input"))?;
+    let start = base + header;
+
+    let der = match tag {
+        INTEGER => {
+            match content {
+                [] => return Err(invalid(start, "empty integer")),
+                [0x00, next, ..] if next & 0x80 == 0 => return Err(invalid(start, "non-minimal integer")),
+                [0xff, next, ..] if next & 0x80 != 0 => return Err(invalid(start, "non-minimal integer")),
+                _ => Der::Integer(content.to_vec()),
+            }
+        }
+        BIT_STRING => match content {
+            [0, rest @ ..] => Der::BitString(rest.to_vec()),
+            [] => return Err(invalid(start, "empty bit string")),
+            _ => return Err(invalid(start, "unsupported unused bits")),
+        },
+        OCTET_STRING => Der::OctetString(content.to_vec()),
+        NULL if content.is_empty() => Der::Null,
+        NULL => return Err(invalid(start, "NULL with content")),
+        OBJECT_IDENTIFIER => Der::Oid(parse_oid(content, start)?),
+        SEQUENCE => {
+            let mut items = Vec::new();
+            let mut offset = 0;
+            while offset < content.len() {
+                let (item, used) = parse(&content[offset..], start + offset, depth + 1)?;
+                items.push(item);
+                offset += used;
+            }
+            Der::Sequence(items)
+        }
+        _ => return Err(invalid(base, "unsupported tag")),
+    };
+
+    Ok((der, header + len))
+}
+
+/// Length octets at `bytes[0]`: the length and how many octets encoded it
+fn parse_length(bytes: &[u8], base: usize) -> Result<(usize, usize), SerializeError> {
+    let &first = bytes.first().ok_or(invalid(base, "missing length"))?;
+    if first < 0x80 {
+        return Ok((first as usize, 1));
+    }
+
+    let count = (first & 0x7f) as usize;
+    if count == 0 {
+        return Err(invalid(base, "indefinite length"));
+    }
+    if count > size_of::<usize>() {
+        return Err(invalid(base, "length too large"));
+    }
+
+    let octets = bytes.get(1..1 + count).ok_or(invalid(base, "truncated length"))?;
+    if octets[0] == 0 {
+        return Err(invalid(base, "non-minimal length"));
+    }
+    let len = octets.iter().fold(0usize, |len, &b| len << 8 | b as usize);
+    if len < 0x80 {
+        return Err(invalid(base, "non-minimal length"));
+    }
+
+    Ok((len, 1 + count))
+}
+
+fn parse_oid(content: &[u8], base: usize) -> Result<Vec<u64>, SerializeError> {
+    let mut values = Vec::new();
+    let mut value = 0u64;
+    let mut fresh = true;
+
+    for (i, &b) in content.iter().enumerate() {
+        if fresh && b == 0x80 {
+            return Err(invalid(base + i, "non-minimal arc"));
+        }
+        if value >> 57 != 0 {
+            return Err(invalid(base + i, "arc too large"));
+        }
+
+        value = value << 7 | (b & 0x7f) as u64;
+        fresh = b & 0x80 == 0;
+        if fresh {
+            values.push(value);
+            value = 0;
+        }
+    }
+
+    if !fresh || values.is_empty() {
+        return Err(invalid(base + content.len(), "truncated object identifier"));
+    }
+
+    // The first value packs two arcs as 40 * first + second
+    let first = values[0];
+    let (a, b) = if first < 80 { (first / 40, first % 40) } else { (2, first - 80) };
+    let mut arcs = vec![a, b];
+    arcs.extend_from_slice(&values[1..]);
+    Ok(arcs)
+}
+
+/// The X.509 wrapper around a public key: which algorithm, its parameters,
+/// and the algorithm's own encoding of the key inside a BIT STRING
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubjectPublicKeyInfo {
+    pub algorithm: Vec<u64>,
+    pub parameters: Option<Der>,
+    pub public_key: Vec<u8>,
+}
+
+impl SubjectPublicKeyInfo {
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut algorithm = vec![Der::Oid(self.algorithm.clone())];
+        algorithm.extend(self.parameters.clone());
+
+        Der::Sequence(vec![Der::Sequence(algorithm), Der::BitString(self.public_key.clone())]).to_der()
+    }
+
+    pub fn from_der(bytes: &[u8]) -> Result<Self, SerializeError> {
+        let der = Der::from_der(bytes)?;
+        let [algorithm, public_key] = der.as_sequence()? else {
+            return Err(invalid(0, "expected algorithm and key"));
+        };
+
+        let (oid, parameters) = match algorithm.as_sequence()? {
+            [oid] => (oid, None),
+            [oid, parameters] => (oid, Some(parameters.clone())),
+            _ => return Err(invalid(0, "expected algorithm identifier")),
+        };
+
+        Ok(Self {
+            algorithm: oid.as_oid()?.to_vec(),
+            parameters,
+            public_key: public_key.as_bit_string()?.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_hex;
+
+    #[test]
+    fn test_known_encodings() {
+        let rsa_encryption = Der::Oid(vec![1, 2, 840, 113549, 1, 1, 1]);
+        assert_eq!(rsa_encryption.to_der(), from_hex("06092a864886f70d010101").unwrap());
+        assert_eq!(Der::from_der(&rsa_encryption.to_der()).unwrap(), rsa_encryption);
+
+        assert_eq!(Der::unsigned(&[0x80]).to_der(), [0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(Der::unsigned(&[0x00, 0x00, 0x7f]).to_der(), [0x02, 0x01, 0x7f]);
+        assert_eq!(Der::unsigned(&[]).to_der(), [0x02, 0x01, 0x00]);
+
+        let long = Der::OctetString(vec![0xaa; 300]);
+        assert_eq!(long.to_der()[..4], [0x04, 0x82, 0x01, 0x2c]);
+        assert_eq!(Der::from_der(&long.to_der()).unwrap(), long);
+    }
+
+    #[test]
+    fn test_round_trip_nested() {
+        let value = Der::Sequence(vec![
+            Der::unsigned(&[0x01, 0x00, 0x01]),
+            Der::Sequence(vec![Der::Oid(vec![2, 999, 3]), Der::Null]),
+            Der::BitString(vec![1, 2, 3]),
+            Der::OctetString(Vec::new()),
+        ]);
+        let encoded = value.to_der();
+
+        assert_eq!(Der::from_der(&encoded).unwrap(), value);
+        assert_eq!(value.as_sequence().unwrap()[0].as_unsigned().unwrap(), [0x01, 0x00, 0x01]);
+    }
+
+    #[test]
+    fn test_rejects_non_der() {
+        let cases: &[(&[u8], usize, &str)] = &[
+            (&[0x02, 0x02, 0x00, 0x01], 2, "non-minimal integer"),
+            (&[0x04, 0x81, 0x05, 1, 2, 3, 4, 5], 1, "non-minimal length"),
+            (&[0x30, 0x80, 0x00, 0x00], 1, "indefinite length"),
+            (&[0x04, 0x05, 0x00], 1, "length past end of input"),
+            (&[0x05, 0x00, 0x00], 2, "trailing data"),
+            (&[0x30, 0x02, 0x02, 0x01], 3, "length past end of input"),
+        ];
+
+        for &(bytes, offset, reason) in cases {
+            assert_eq!(Der::from_der(bytes), Err(SerializeError::InvalidDer { offset, reason }));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use colored::{Color, Colorize};
+
+/// Colors handed out to repeated blocks, in order of first appearance
+const PALETTE: [Color; 6] = [Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan];
+
+fn printable(b: u8) -> char {
+    if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' }
+}
+
+/// `xxd`-style dump: offset, sixteen bytes in pairs, then the printable ones
+pub fn hexdump(bytes: &[u8]) -> String {
+    let mut result = String::new();
+    for (row, line) in bytes.chunks(16).enumerate() {
+        write!(result, "{:08x}:", row * 16).unwrap();
+        for i in 0..16 {
+            if i % 2 == 0 {
+                result.push(' ');
+            }
+            match line.get(i) {
+                Some(b) => write!(result, "{:02x}", b).unwrap(),
+                None => result.push_str("  "),
+            }
+        }
+        result.push_str("  ");
+        result.extend(line.iter().map(|&b| printable(b)));
+        result.push('\n');
+    }
+    result
+}
+
+/// One block per row, with every block that occurs more than once drawn in
+/// its own color so ECB repeats stand out. A short final block is shown as is.
+pub fn block_grid(bytes: &[u8], block_size: usize) -> String {
+    assert!(block_size > 0, "block size must be nonzero");
+
+    let mut counts: HashMap<&[u8], usize> = HashMap::new();
+    for block in bytes.chunks(block_size) {
+        *counts.entry(block).or_default() += 1;
+    }
+
+    let mut colors: HashMap<&[u8], Color> = HashMap::new();
+    let mut result = String::new();
+    for (index, block) in bytes.chunks(block_size).enumerate() {
+        let hex = hex_cells(block, block_size);
+        let hex = if counts[block] > 1 {
+            let next = PALETTE[colors.len() % PALETTE.len()];
+            hex.color(*colors.entry(block).or_insert(next)).bold().to_string()
+        } else {
+            hex
+        };
+        let ascii: String = block.iter().map(|&b| printable(b)).collect();
+        writeln!(result, "{:4} | {} | {}", index, hex, ascii).unwrap();
+    }
+    result
+}
+
+/// Block-by-block comparison of two ciphertexts for bit-flipping work.
+/// Matching blocks get a single row; differing ones show `-` for `old` and
+/// `+` for `new` with the changed bytes highlighted. Bytes past the end of
+/// the shorter input count as changed.
+pub fn diff_blocks(old: &[u8], new: &[u8], block_size: usize) -> String {
+    assert!(block_size > 0, "block size must be nonzero");
+
+    let mut result = String::new();
+    let blocks = old.len().max(new.len()).div_ceil(block_size);
+    for index in 0..blocks {
+        let range = |bytes: &[u8]| {
+            let start = (index * block_size).min(bytes.len());
+            let end = ((index + 1) * block_size).min(bytes.len());
+            start..end
+        };
+        let (a, b) = (&old[range(old)], &new[range(new)]);
+
+        if a == b {
+            writeln!(result, "{:4}   {}", index, hex_cells(a, block_size)).unwrap();
+            continue;
+        }
+        writeln!(result, "{:4} - {}", index, highlighted(a, b, block_size, Color::Red)).unwrap();
+        writeln!(result, "{:4} + {}", index, highlighted(b, a, block_size, Color::Green)).unwrap();
+    }
+    result
+}
+
+/// Space-separated hex bytes, padded out to a full block
+fn hex_cells(block: &[u8], block_size: usize) -> String {
+    (0..block_size)
+        .map(|i| block.get(i).map_or("  ".to_string(), |b| format!("{:02x}", b)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `hex_cells`, coloring the bytes of `block` that differ from `other`
+fn highlighted(block: &[u8], other: &[u8], block_size: usize, color: Color) -> String {
+    (0..block_size)
+        .map(|i| match block.get(i) {
+            Some(b) if other.get(i) == Some(b) => format!("{:02x}", b),
+            Some(b) => format!("{:02x}", b).color(color).bold().to_string(),
+            None => "  ".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Mutex, MutexGuard};
+
+    static COLOR: Mutex<()> = Mutex::new(());
+
+    /// Forces color on until dropped. The override is process-wide, so tests
+    /// that rely on it take turns and put it back afterwards.
+    struct ForceColor {
+        _guard: MutexGuard<'static, ()>,
+    }
+
+    impl ForceColor {
+        fn new() -> Self {
+            let guard = COLOR.lock().unwrap_or_else(|e| e.into_inner());
+            colored::control::set_override(true);
+            Self { _guard: guard }
+        }
+    }
+
+    impl Drop for ForceColor {
+        fn drop(&mut self) {
+            colored::control::unset_override();
+        }
+    }
+
+    #[test]
+    fn test_hexdump() {
+        let dump = hexdump(b"I'm killing your brain\x00\xff");
+        assert_eq!(
+            dump,
+            "00000000: 4927 6d20 6b69 6c6c 696e 6720 796f 7572  I'm killing your\n\
+             00000010: 2062 7261 696e 00ff                       brain..\n"
+        );
+        assert_eq!(hexdump(b""), "");
+    }
+
+    #[test]
+    fn test_block_grid_colors_repeats() {
+        let _color = ForceColor::new();
+
+        let mut bytes = b"YELLOW SUBMARINE".repeat(2);
+        bytes.extend_from_slice(b"0123456789abcdef!");
+        let grid = block_grid(&bytes, 16);
+        let rows: Vec<&str> = grid.lines().collect();
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], rows[1].replacen("   1", "   0", 1));
+        assert!(rows[0].contains("\x1b["));
+        assert!(!rows[2].contains("\x1b["));
+        assert!(rows[3].starts_with("   3 | 21    "));
+    }
+
+    #[test]
+    fn test_diff_blocks() {
+        let _color = ForceColor::new();
+
+        let old = [0u8; 32];
+        let mut new = old;
+        new[20] ^= 0x01;
+        let diff = diff_blocks(&old, &new, 16);
+        let rows: Vec<&str> = diff.lines().collect();
+
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].starts_with("   0   00 00") && !rows[0].contains("\x1b["));
+        assert!(rows[1].starts_with("   1 - 00 00 00 00 \x1b["));
+        assert!(rows[2].starts_with("   1 + 00 00 00 00 \x1b[") && rows[2].contains("01"));
+
+        // A truncated ciphertext shows the missing tail as changed
+        assert_eq!(diff_blocks(&old, &old[..16], 16).lines().count(), 3);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::SerializeError;
+
+const LOWER: &[u8; 16] = b"0123456789abcdef";
+const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Marks bytes that aren't hex digits in `DECODE`
+const INVALID: u8 = 0xff;
+
+/// Nibble value of every byte, or `INVALID`
+const DECODE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < 16 {
+        table[LOWER[i] as usize] = i as u8;
+        table[UPPER[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// What the decoder tolerates besides pairs of hex digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HexConfig {
+    /// A leading `0x` or `0X`
+    pub prefix: bool,
+    /// `:` or space between bytes, as in `de:ad:be:ef` or `de ad be ef`
+    pub separators: bool,
+    /// `\r` or `\n` between bytes, for line-wrapped dumps and files ending
+    /// in a newline
+    pub line_breaks: bool,
+}
+
+impl HexConfig {
+    /// Bare digit pairs only
+    pub const STRICT: Self = Self { prefix: false, separators: false, line_breaks: false };
+
+    /// Also accepts what people paste from debuggers and certificate dumps
+    pub const LENIENT: Self = Self { prefix: true, separators: true, line_breaks: true };
+
+    pub fn with_line_breaks(self, line_breaks: bool) -> Self {
+        Self { line_breaks, ..self }
+    }
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    encode(bytes, LOWER)
+}
+
+pub fn to_hex_upper(bytes: &[u8]) -> String {
+    encode(bytes, UPPER)
+}
+
+fn encode(bytes: &[u8], digits: &[u8; 16]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for &b in bytes {
+        result.push(digits[(b >> 4) as usize] as char);
+        result.push(digits[(b & 0xf) as usize] as char);
+    }
+    result
+}
+
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, SerializeError> {
+    from_hex_with(hex.as_bytes(), &HexConfig::STRICT)
+}
+
+/// Decodes under `config`. Errors carry the index into `hex` of the bad byte,
+/// or its length if it ends halfway through a byte.
+pub fn from_hex_with(hex: &[u8], config: &HexConfig) -> Result<Vec<u8>, SerializeError> {
+    let mut result = Vec::with_capacity(hex.len() / 2);
+    let mut decoder = Decoder::new(*config);
+    decoder.feed(hex, |b| {
+        result.push(b);
+        Ok(())
+    })?;
+    decoder.finish()?;
+    Ok(result)
+}
+
+/// Decodes into `out` without allocating and returns how many bytes were
+/// written. `InvalidLength` if `out` is too short.
+pub fn decode_into(hex: &[u8], config: &HexConfig, out: &mut [u8]) -> Result<usize, SerializeError> {
+    let mut written = 0;
+    let mut decoder = Decoder::new(*config);
+    decoder.feed(hex, |b| {
+        *out.get_mut(written).ok_or(SerializeError::InvalidLength)? = b;
+        written += 1;
+        Ok(())
+    })?;
+    decoder.finish()?;
+    Ok(written)
+}
+
+/// Decoding state that can be fed input in pieces split anywhere, for the
+/// streaming reader. Offsets in errors count from the start of all input.
+pub(crate) struct Decoder {
+    config: HexConfig,
+    high: Option<u8>,
+    offset: usize,
+}
+
+impl Decoder {
+    pub(crate) fn new(config: HexConfig) -> Self {
+        Self { config, high: None, offset: 0 }
+    }
+
+    pub(crate) fn feed<F>(&mut self, input: &[u8], mut emit: F) -> Result<(), SerializeError>
+    where
+        F: FnMut(u8) -> Result<(), SerializeError>,
+    {
+        for &c in input {
+            let offset = self.offset;
+            self.offset += 1;
+
+            // The 0 of a 0x prefix was taken as a high nibble; drop it
+            if self.config.prefix && offset == 1 && self.high == Some(0) && (c == b'x' || c == b'X') {
+                self.high = None;
+                continue;
+            }
+
+            // Separators only go between bytes, never inside one
+            if self.config.separators && self.high.is_none() && (c == b':' || c == b' ') {
+                continue;
+            }
+            if self.config.line_breaks && self.high.is_none() && (c == b'\r' || c == b'\n') {
+                continue;
+            }
+
+            let nibble = DECODE[c as usize];
+            if nibble == INVALID {
+                return Err(SerializeError::InvalidCharacter { offset, byte: c });
+            }
+
+            match self.high.take() {
+                Some(h) => emit(h << 4 | nibble)?,
+                None => self.high = Some(nibble),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn finish(&self) -> Result<(), SerializeError> {
+        match self.high {
+            Some(_) => Err(SerializeError::TruncatedInput { offset: self.offset }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(from_hex(&to_hex(&bytes)).unwrap(), bytes);
+        assert_eq!(from_hex(&to_hex_upper(&bytes)).unwrap(), bytes);
+        assert_eq!(to_hex_upper(&[0xab, 0x01]), "AB01");
+    }
+
+    #[test]
+    fn test_lenient_input() {
+        let lenient = HexConfig::LENIENT;
+        assert_eq!(from_hex_with(b"0xDEADbeef", &lenient).unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(from_hex_with(b"de:ad be:ef", &lenient).unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(from_hex_with(b"dead\r\nbeef\n", &lenient).unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+
+        assert_eq!(from_hex("0xde"), Err(SerializeError::InvalidCharacter { offset: 1, byte: b'x' }));
+        assert_eq!(from_hex_with(b"d:e", &lenient), Err(SerializeError::InvalidCharacter { offset: 1, byte: b':' }));
+        assert_eq!(from_hex_with(b"d\ne", &lenient), Err(SerializeError::InvalidCharacter { offset: 1, byte: b'\n' }));
+        assert_eq!(from_hex("de\n"), Err(SerializeError::InvalidCharacter { offset: 2, byte: b'\n' }));
+    }
+
+    #[test]
+    fn test_errors_carry_offsets() {
+        assert_eq!(from_hex("00ag"), Err(SerializeError::InvalidCharacter { offset: 3, byte: b'g' }));
+        assert_eq!(from_hex("abc"), Err(SerializeError::TruncatedInput { offset: 3 }));
+    }
+
+    #[test]
+    fn test_decode_into() {
+        let mut out = [0u8; 4];
+        assert_eq!(decode_into(b"0102", &HexConfig::STRICT, &mut out), Ok(2));
+        assert_eq!(out[..2], [1, 2]);
+        assert_eq!(decode_into(b"0102030405", &HexConfig::STRICT, &mut out), Err(SerializeError::InvalidLength));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_round_trip(bytes in prop::collection::vec(any::<u8>(), 0..256)) {
+            prop_assert_eq!(from_hex(&to_hex(&bytes)).unwrap(), bytes.as_slice());
+            prop_assert_eq!(from_hex(&to_hex_upper(&bytes)).unwrap(), bytes.as_slice());
+            prop_assert_eq!(from_hex_with(to_hex(&bytes).as_bytes(), &HexConfig::LENIENT).unwrap(), bytes);
+        }
+    }
+}
@@ -1,6 +1,32 @@
-use std::fs;
-
-const BASE64_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+use std::fs::File;
+use std::io::{self, Read};
+
+mod base32;
+mod base58;
+mod base64;
+mod base85;
+mod codec;
+mod convert;
+pub mod der;
+mod dump;
+mod hex;
+pub mod load;
+mod pem;
+mod percent;
+mod stream;
+
+pub use base32::{from_base32, to_base32};
+pub use base58::{from_base58, to_base58};
+pub use base64::{from_base64, from_base64_with, to_base64, to_base64_with, Alphabet, Base64Config, Padding, Whitespace};
+pub use base85::{from_base85, to_base85};
+pub use codec::{Codec, Encoding};
+pub use convert::{Base64, Hex};
+pub use der::{Der, SubjectPublicKeyInfo};
+pub use dump::{block_grid, diff_blocks, hexdump};
+pub use hex::{decode_into, from_hex, from_hex_with, to_hex, to_hex_upper, HexConfig};
+pub use pem::Pem;
+pub use percent::{percent_decode, percent_encode};
+pub use stream::{Base64Reader, Base64Writer, HexReader, HexWriter};
 
 pub trait Serialize {
     fn to_bytes(&self) -> Vec<u8>;
@@ -9,7 +35,24 @@ pub trait Serialize {
 }
 
 pub trait FromHex {
-    fn from_hex(&self) -> Vec<u8>;
+    #[allow(clippy::wrong_self_convention)]
+    fn from_hex(&self) -> Result<Vec<u8>, SerializeError>;
+}
+
+/// `bytes.to_hex()` for anything that views as bytes
+pub trait ToHex {
+    fn to_hex(&self) -> String;
+}
+
+/// `bytes.to_base64()` for anything that views as bytes
+pub trait ToBase64 {
+    fn to_base64(&self) -> String;
+}
+
+/// `s.from_base64()` on string types
+pub trait FromBase64 {
+    #[allow(clippy::wrong_self_convention)]
+    fn from_base64(&self) -> Result<Vec<u8>, SerializeError>;
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -17,6 +60,18 @@ pub enum SerializeError {
     InvalidLength,
     InvalidFormat,
     InvalidData(String),
+    /// A byte outside the alphabet at this offset
+    InvalidCharacter { offset: usize, byte: u8 },
+    /// Padding misplaced, missing or not allowed, at this offset
+    InvalidPadding { offset: usize },
+    /// Input ends (at this offset) partway through an encoded unit
+    TruncatedInput { offset: usize },
+    /// Nonzero unused trailing bits in the character at this offset
+    NonCanonical { offset: usize },
+    /// Malformed or non-canonical DER at this offset
+    InvalidDer { offset: usize, reason: &'static str },
+    /// Reading or writing the underlying file or stream failed
+    Io { kind: io::ErrorKind, message: String },
 }
 
 impl std::fmt::Display for SerializeError {
@@ -25,150 +80,47 @@ impl std::fmt::Display for SerializeError {
             SerializeError::InvalidLength => write!(f, "Invalid data length"),
             SerializeError::InvalidFormat => write!(f, "Invalid data format"),
             SerializeError::InvalidData(msg) => write!(f, "Invalid data: {}", msg),
+            SerializeError::InvalidCharacter { offset, byte } => {
+                write!(f, "Invalid character {:?} at offset {}", *byte as char, offset)
+            }
+            SerializeError::InvalidPadding { offset } => write!(f, "Invalid padding at offset {}", offset),
+            SerializeError::TruncatedInput { offset } => write!(f, "Input truncated at offset {}", offset),
+            SerializeError::NonCanonical { offset } => write!(f, "Non-canonical trailing bits at offset {}", offset),
+            SerializeError::InvalidDer { offset, reason } => write!(f, "Invalid DER at offset {}: {}", offset, reason),
+            SerializeError::Io { message, .. } => write!(f, "I/O error: {}", message),
         }
     }
 }
 
 impl std::error::Error for SerializeError {}
 
-pub fn to_hex(bytes: &[u8]) -> String {
-    bytes.iter()
-        .map(|b| format!("{:02x}", b))
-        .collect()
-}
-
-pub fn from_hex(hex: &str) -> Result<Vec<u8>, SerializeError> {
-    if hex.len() % 2 != 0 {
-        return Err(SerializeError::InvalidLength);
-    }
-    
-    hex.chars()
-        .collect::<Vec<_>>()
-        .chunks(2)
-        .map(|chunk| {
-            let hex_pair: String = chunk.iter().collect();
-            u8::from_str_radix(&hex_pair, 16)
-                .map_err(|_| SerializeError::InvalidFormat)
-        })
-        .collect()
-}
-
-pub fn to_base64(bytes: &[u8]) -> String {
-    let mut result = String::new();
-
-    for chunk in bytes.chunks(3) {
-        match chunk.len() {
-            3 => {
-                let b1 = chunk[0] as u32;
-                let b2 = chunk[1] as u32;
-                let b3 = chunk[2] as u32;
-
-                let combined = (b1 << 16) | (b2 << 8) | b3;
-
-                let idx1 = ((combined >> 18) & 0x3F) as usize;
-                let idx2 = ((combined >> 12) & 0x3F) as usize;
-                let idx3 = ((combined >> 6) & 0x3F) as usize;
-                let idx4 = (combined & 0x3F) as usize;
-
-                result.push(BASE64_CHARS[idx1] as char);
-                result.push(BASE64_CHARS[idx2] as char);
-                result.push(BASE64_CHARS[idx3] as char);
-                result.push(BASE64_CHARS[idx4] as char);
-            }
-            2 => {
-                let b1 = chunk[0] as u32;
-                let b2 = chunk[1] as u32;
-
-                let combined = (b1 << 16) | (b2 << 8);
-
-                let idx1 = ((combined >> 18) & 0x3F) as usize;
-                let idx2 = ((combined >> 12) & 0x3F) as usize; 
-                let idx3 = ((combined >> 6) & 0x3F) as usize;
-
-                result.push(BASE64_CHARS[idx1] as char);
-                result.push(BASE64_CHARS[idx2] as char);
-                result.push(BASE64_CHARS[idx3] as char);
-                result.push('=');
-            }
-            1 => {
-                let b1 = chunk[0] as u32;
-
-                let combined = b1 << 16;
-
-                let idx1 = ((combined >> 18) & 0x3F) as usize;
-                let idx2 = ((combined >> 12) & 0x3F) as usize;
-
-                result.push(BASE64_CHARS[idx1] as char);
-                result.push(BASE64_CHARS[idx2] as char);
-                result.push('=');
-                result.push('=');
-            }
-            _ => {
-                unreachable!();
-            }
+impl SerializeError {
+    /// The same error with its offset moved by `base`, for input decoded out
+    /// of the middle of something larger
+    pub(crate) fn shifted(self, base: usize) -> Self {
+        match self {
+            SerializeError::InvalidCharacter { offset, byte } => SerializeError::InvalidCharacter { offset: offset + base, byte },
+            SerializeError::InvalidPadding { offset } => SerializeError::InvalidPadding { offset: offset + base },
+            SerializeError::TruncatedInput { offset } => SerializeError::TruncatedInput { offset: offset + base },
+            SerializeError::NonCanonical { offset } => SerializeError::NonCanonical { offset: offset + base },
+            SerializeError::InvalidDer { offset, reason } => SerializeError::InvalidDer { offset: offset + base, reason },
+            other => other,
         }
     }
-
-    result
 }
 
-pub fn from_base64(base64: &str) -> Result<Vec<u8>, SerializeError> {
-    let clean_input: String = base64.chars()
-        .filter(|c| !c.is_whitespace())
-        .collect();
-
-    let mut result = Vec::new();
-
-    for chunk in clean_input.as_bytes().chunks(4) {
-        match chunk.len() {
-            4 => {
-                // Convert each base64 char to its 6-bit value
-                let v1 = char_to_base64_value(chunk[0] as char)?;
-                let v2 = char_to_base64_value(chunk[1] as char)?;
-                let v3 = char_to_base64_value(chunk[2] as char)?;
-                let v4 = char_to_base64_value(chunk[3] as char)?;
-
-                // Pack four 6-bit values into 24 bits
-                let combined = (v1 << 18) | (v2 << 12) | (v3 << 6) | v4;
-
-                // Extract 3 bytes
-                let b1 = ((combined >> 16) & 0xFF) as u8;
-                let b2 = ((combined >> 8) & 0xFF) as u8;
-                let b3 = (combined & 0xFF) as u8;
-
-                // Handle padding
-                if chunk[3] == b'=' && chunk[2] == b'=' {
-                    result.push(b1);
-                } else if chunk[3] == b'=' {
-                    result.push(b1);
-                    result.push(b2);
-                } else {
-                    result.push(b1);
-                    result.push(b2);
-                    result.push(b3);
-                }
-            }
-        _ => return Err(SerializeError::InvalidLength),
+impl From<io::Error> for SerializeError {
+    /// Decoding errors raised inside the stream adapters come back out as themselves
+    fn from(e: io::Error) -> Self {
+        if let Some(inner) = e.get_ref().and_then(|inner| inner.downcast_ref::<SerializeError>()) {
+            return inner.clone();
         }
-    }
-
-    Ok(result)
-}
-
-fn char_to_base64_value(c: char) -> Result<u32, SerializeError> {
-    match c {
-        'A'..='Z' => Ok((c as u32) - ('A' as u32)),
-        'a'..='z' => Ok((c as u32) - ('a' as u32) + 26),
-        '0'..='9' => Ok((c as u32) - ('0' as u32) + 52),
-        '+' => Ok(62),
-        '/' => Ok(63),
-        '=' => Ok(0), // Padding - value doesn't matter
-        _ => Err(SerializeError::InvalidData(format!("Invalid Base64 character: {}", c))),
+        SerializeError::Io { kind: e.kind(), message: e.to_string() }
     }
 }
 
 pub fn from_base64_file(path: &str) -> Result<Vec<u8>, SerializeError> {
-    let base64_content = fs::read_to_string(path).map_err(|_| SerializeError::InvalidFormat)?;
-    let bytes = from_base64(&base64_content)?;
+    let mut bytes = Vec::new();
+    Base64Reader::new(File::open(path)?).read_to_end(&mut bytes)?;
     Ok(bytes)
 }
\ No newline at end of file
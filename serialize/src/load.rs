@@ -0,0 +1,262 @@
+//! Loading challenge data without knowing its format up front.
+//!
+//! The sniffer only picks between the formats challenge files come in: hex,
+//! Base64 (standard or URL-safe) and raw bytes, either as one blob or one
+//! item per line. It prefers the most specific format that decodes, so
+//! `deadbeef` is hex even though it is also valid Base64.
+
+use std::fs;
+
+use crate::{Base64Config, Codec, Encoding, HexConfig, Padding, SerializeError};
+
+/// Text that decodes as nothing else is only a guess at raw bytes, however
+/// long it is
+const RAW_TEXT_CONFIDENCE: f64 = 0.5;
+
+/// What the sniffer settled on, and how sure it is (0 to 1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detection {
+    pub encoding: Encoding,
+    pub confidence: f64,
+}
+
+/// Decoded data with the format it was decoded from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loaded<T> {
+    pub data: T,
+    pub detection: Detection,
+}
+
+/// Decoding options. The defaults sniff the format and accept any guess.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Loader {
+    encoding: Option<Encoding>,
+    min_confidence: f64,
+}
+
+impl Loader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skip sniffing and decode as `encoding`, e.g. for a short key that could
+    /// be hex or Base64
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    /// Fail instead of guessing when the sniffer is less sure than this
+    pub fn min_confidence(mut self, confidence: f64) -> Self {
+        self.min_confidence = confidence;
+        self
+    }
+
+    /// The whole input as one encoded blob; line breaks are insignificant
+    pub fn bytes(&self, input: &[u8]) -> Result<Loaded<Vec<u8>>, SerializeError> {
+        let detection = self.detect(input, false)?;
+        let data = decode(input, detection.encoding)?;
+        Ok(Loaded { data, detection })
+    }
+
+    /// One item per non-empty line, all in the same format
+    pub fn lines(&self, input: &[u8]) -> Result<Loaded<Vec<Vec<u8>>>, SerializeError> {
+        let detection = self.detect(input, true)?;
+        if detection.encoding == Encoding::Raw {
+            let data = input.split(|&b| b == b'\n').filter(|line| !line.is_empty()).map(<[u8]>::to_vec).collect();
+            return Ok(Loaded { data, detection });
+        }
+
+        let data = significant_lines(input)
+            .map(|line| decode(line, detection.encoding))
+            .collect::<Result<_, _>>()?;
+        Ok(Loaded { data, detection })
+    }
+
+    pub fn file(&self, path: &str) -> Result<Loaded<Vec<u8>>, SerializeError> {
+        self.bytes(&fs::read(path)?)
+    }
+
+    pub fn file_lines(&self, path: &str) -> Result<Loaded<Vec<Vec<u8>>>, SerializeError> {
+        self.lines(&fs::read(path)?)
+    }
+
+    fn detect(&self, input: &[u8], by_line: bool) -> Result<Detection, SerializeError> {
+        if let Some(encoding) = self.encoding {
+            return Ok(Detection { encoding, confidence: 1.0 });
+        }
+
+        let detection = if by_line { detect_lines(input) } else { detect(input) };
+        if detection.confidence < self.min_confidence {
+            return Err(SerializeError::InvalidData(format!(
+                "looks like {} but only with confidence {:.2}; set the encoding explicitly",
+                detection.encoding, detection.confidence
+            )));
+        }
+        Ok(detection)
+    }
+}
+
+/// Sniffs `input` as one blob
+pub fn detect(input: &[u8]) -> Detection {
+    sniff(input, std::iter::once(input))
+}
+
+/// Sniffs `input` as one item per line
+pub fn detect_lines(input: &[u8]) -> Detection {
+    sniff(input, significant_lines(input))
+}
+
+/// Lines with surrounding whitespace removed, skipping blank ones
+fn significant_lines(input: &[u8]) -> impl Iterator<Item = &[u8]> + Clone {
+    input.split(|&b| b == b'\n').map(<[u8]>::trim_ascii).filter(|line| !line.is_empty())
+}
+
+fn decode(input: &[u8], encoding: Encoding) -> Result<Vec<u8>, SerializeError> {
+    match encoding {
+        Encoding::Raw => Ok(input.to_vec()),
+        // Hex wrapped across lines, or pasted with a 0x prefix and separators
+        Encoding::Hex => {
+            let digits: Vec<u8> = input.iter().copied().filter(|b| !b.is_ascii_whitespace()).collect();
+            crate::from_hex_with(&digits, &HexConfig::STRICT)
+                .or_else(|_| crate::from_hex_with(input.trim_ascii(), &HexConfig::LENIENT))
+        }
+        _ => {
+            let text = std::str::from_utf8(input).map_err(|e| SerializeError::InvalidCharacter {
+                offset: e.valid_up_to(),
+                byte: input[e.valid_up_to()],
+            })?;
+            codec(encoding).decode(text)
+        }
+    }
+}
+
+/// How the loader decodes each sniffed format: URL-safe Base64 with or
+/// without padding, since both turn up
+fn codec(encoding: Encoding) -> Box<dyn Codec> {
+    struct UrlSafe;
+
+    impl Codec for UrlSafe {
+        fn encode(&self, bytes: &[u8]) -> String {
+            Encoding::Base64Url.encode(bytes)
+        }
+
+        fn decode(&self, text: &str) -> Result<Vec<u8>, SerializeError> {
+            let config = Base64Config::URL_SAFE.with_padding(Padding::Optional);
+            crate::from_base64_with(text.trim(), &config)
+        }
+    }
+
+    match encoding {
+        Encoding::Base64Url => Box::new(UrlSafe),
+        other => Box::new(other),
+    }
+}
+
+/// Tries each format from most to least specific over every item. The
+/// confidence grows with the number of significant characters: short strings
+/// fit several formats by accident, long ones almost never do. Hex is judged
+/// more strictly per character since only 22 of the 64 Base64 characters are
+/// hex digits.
+fn sniff<'a>(input: &[u8], items: impl Iterator<Item = &'a [u8]> + Clone) -> Detection {
+    let binary = std::str::from_utf8(input).is_err()
+        || input.iter().any(|&b| b.is_ascii_control() && !b.is_ascii_whitespace());
+    if binary {
+        return Detection { encoding: Encoding::Raw, confidence: 1.0 };
+    }
+
+    let significant = input.iter().filter(|b| !b.is_ascii_whitespace()).count();
+    if significant == 0 {
+        return Detection { encoding: Encoding::Raw, confidence: 0.0 };
+    }
+
+    let evidence = |per_char: f64| 1.0 - (-(significant as f64) / per_char).exp2();
+    let decodes = |encoding| items.clone().all(|item| decode(item, encoding).is_ok());
+
+    if decodes(Encoding::Hex) {
+        return Detection { encoding: Encoding::Hex, confidence: evidence(4.0) };
+    }
+
+    for encoding in [Encoding::Base64, Encoding::Base64Url] {
+        if decodes(encoding) {
+            return Detection { encoding, confidence: evidence(8.0) };
+        }
+    }
+
+    // Text that fits none of them, e.g. with spaces or punctuation inside a line
+    Detection { encoding: Encoding::Raw, confidence: RAW_TEXT_CONFIDENCE }
+}
+
+/// Sniffs and decodes with the default `Loader`
+pub fn bytes(input: &[u8]) -> Result<Loaded<Vec<u8>>, SerializeError> {
+    Loader::new().bytes(input)
+}
+
+pub fn lines(input: &[u8]) -> Result<Loaded<Vec<Vec<u8>>>, SerializeError> {
+    Loader::new().lines(input)
+}
+
+pub fn file(path: &str) -> Result<Loaded<Vec<u8>>, SerializeError> {
+    Loader::new().file(path)
+}
+
+pub fn file_lines(path: &str) -> Result<Loaded<Vec<Vec<u8>>>, SerializeError> {
+    Loader::new().file_lines(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_each_format() {
+        let hex = bytes(b"49276d206b696c6c696e6720796f757220627261696e\n").unwrap();
+        assert_eq!(hex.detection.encoding, Encoding::Hex);
+        assert!(hex.detection.confidence > 0.99);
+        assert_eq!(hex.data, b"I'm killing your brain");
+
+        let base64 = bytes(b"SSdtIGtpbGxpbmcgeW91\nciBicmFpbg==\n").unwrap();
+        assert_eq!(base64.detection.encoding, Encoding::Base64);
+        assert_eq!(base64.data, b"I'm killing your brain");
+
+        let url = bytes(b"eyJhbGciOiJIUzI1NiJ9-_8").unwrap();
+        assert_eq!(url.detection.encoding, Encoding::Base64Url);
+
+        let binary = bytes(&[0x00, 0x9f, 0x92, 0x96]).unwrap();
+        assert_eq!(binary.detection, Detection { encoding: Encoding::Raw, confidence: 1.0 });
+        assert_eq!(binary.data, [0x00, 0x9f, 0x92, 0x96]);
+
+        let lenient = bytes(b"0xdeadbeef").unwrap();
+        assert_eq!(lenient.detection.encoding, Encoding::Hex);
+        assert_eq!(lenient.data, [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(detect(b"de:ad:be:ef").encoding, Encoding::Hex);
+
+        let text = b"comment1=cooking MCs;userdata=";
+        assert_eq!(detect(text), Detection { encoding: Encoding::Raw, confidence: RAW_TEXT_CONFIDENCE });
+        assert!(Loader::new().min_confidence(0.9).bytes(text).is_err());
+    }
+
+    #[test]
+    fn test_lines() {
+        let loaded = lines(b"0102\n\n  0a0b0c  \n").unwrap();
+        assert_eq!(loaded.detection.encoding, Encoding::Hex);
+        assert_eq!(loaded.data, [vec![1, 2], vec![10, 11, 12]]);
+
+        // Each line must stand on its own: this only works as a single blob
+        assert_eq!(detect_lines(b"QUJD\nRA==\nRQ==").encoding, Encoding::Base64);
+        assert_eq!(detect_lines(b"QUJDR\nA==").encoding, Encoding::Raw);
+    }
+
+    #[test]
+    fn test_override_for_ambiguous_input() {
+        // Four characters: hex for two bytes, or Base64 for three
+        let guess = bytes(b"beef").unwrap();
+        assert_eq!(guess.detection.encoding, Encoding::Hex);
+        assert!(guess.detection.confidence < 0.7);
+        assert!(Loader::new().min_confidence(0.9).bytes(b"beef").is_err());
+
+        let forced = Loader::new().encoding(Encoding::Base64).bytes(b"beef").unwrap();
+        assert_eq!(forced.data, [0x6d, 0xe7, 0x9f]);
+        assert_eq!(forced.detection.confidence, 1.0);
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+use crate::{from_base64, Base64Writer, SerializeError};
+
+/// Base64 line width RFC 7468 asks encoders to use
+const LINE_WIDTH: usize = 64;
+
+/// A labelled block of DER in textual armor (RFC 7468):
+///
+/// ```text
+/// -----BEGIN RSA PUBLIC KEY-----
+/// MIGJAoGBALq6Q7...
+/// -----END RSA PUBLIC KEY-----
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pem {
+    pub label: String,
+    pub contents: Vec<u8>,
+}
+
+impl Pem {
+    pub fn new(label: impl Into<String>, contents: Vec<u8>) -> Self {
+        Self { label: label.into(), contents }
+    }
+
+    pub fn encode(&self) -> String {
+        let mut writer = Base64Writer::new(Vec::new()).wrap(LINE_WIDTH);
+        writer.write_all(&self.contents).unwrap();
+        let body = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        format!("-----BEGIN {0}-----\n{1}\n-----END {0}-----\n", self.label, body)
+    }
+
+    /// The first block in `text`; anything around it is ignored
+    pub fn parse(text: &str) -> Result<Self, SerializeError> {
+        Ok(parse_block(text)?.0)
+    }
+
+    /// Every block in `text`, in order, as in a certificate chain
+    pub fn parse_all(mut text: &str) -> Result<Vec<Self>, SerializeError> {
+        let mut blocks = Vec::new();
+        while text.contains("-----BEGIN ") {
+            let (pem, rest) = parse_block(text)?;
+            blocks.push(pem);
+            text = rest;
+        }
+        Ok(blocks)
+    }
+
+    /// Checks the label before handing back the contents
+    pub fn expect(self, label: &str) -> Result<Vec<u8>, SerializeError> {
+        if self.label != label {
+            return Err(SerializeError::InvalidData(format!("expected {}, found {}", label, self.label)));
+        }
+        Ok(self.contents)
+    }
+}
+
+/// One block and whatever follows it
+fn parse_block(text: &str) -> Result<(Pem, &str), SerializeError> {
+    let missing = |what: &str| SerializeError::InvalidData(format!("PEM: missing {}", what));
+
+    let begin = text.find("-----BEGIN ").ok_or_else(|| missing("BEGIN line"))?;
+    let after_begin = &text[begin + 11..];
+    let label_end = after_begin.find("-----").ok_or_else(|| missing("end of BEGIN line"))?;
+    let label = &after_begin[..label_end];
+
+    let body = &after_begin[label_end + 5..];
+    let end_line = format!("-----END {}-----", label);
+    let end = body.find(&end_line).ok_or_else(|| missing(&end_line))?;
+
+    // Offsets in Base64 errors count from the start of `text`
+    let body_start = text.len() - body.len();
+    let contents = from_base64(&body[..end]).map_err(|e| e.shifted(body_start))?;
+
+    Ok((Pem::new(label, contents), &body[end + end_line.len()..]))
+}
+
+impl fmt::Display for Pem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl FromStr for Pem {
+    type Err = SerializeError;
+
+    fn from_str(s: &str) -> Result<Self, SerializeError> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let pem = Pem::new("TEST DATA", (0..100).collect());
+        let text = pem.encode();
+
+        assert!(text.starts_with("-----BEGIN TEST DATA-----\n"));
+        assert!(text.lines().all(|line| line.len() <= 64));
+        assert_eq!(text.parse::<Pem>().unwrap(), pem);
+    }
+
+    #[test]
+    fn test_parse_all_and_errors() {
+        let text = format!("junk\n{}between\n{}", Pem::new("A", vec![1]), Pem::new("B", vec![2, 3]));
+        let blocks = Pem::parse_all(&text).unwrap();
+        assert_eq!(blocks.iter().map(|b| b.label.as_str()).collect::<Vec<_>>(), ["A", "B"]);
+        assert_eq!(blocks[1].clone().expect("B").unwrap(), [2, 3]);
+        assert!(blocks[0].clone().expect("B").is_err());
+
+        assert!(Pem::parse("-----BEGIN A-----\nAQ==\n-----END B-----\n").is_err());
+        assert_eq!(
+            Pem::parse("-----BEGIN A-----\nA!==\n-----END A-----\n"),
+            Err(SerializeError::InvalidCharacter { offset: 19, byte: b'!' })
+        );
+    }
+}
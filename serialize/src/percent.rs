@@ -0,0 +1,71 @@
+use crate::SerializeError;
+
+const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// RFC 3986 section 2.3: the characters that never need escaping
+fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~')
+}
+
+/// Escapes every byte but the unreserved characters as `%XX`
+pub fn percent_encode(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len());
+    for &b in bytes {
+        if is_unreserved(b) {
+            result.push(b as char);
+        } else {
+            result.push('%');
+            result.push(UPPER[(b >> 4) as usize] as char);
+            result.push(UPPER[(b & 0xf) as usize] as char);
+        }
+    }
+    result
+}
+
+/// Undoes `%XX` escapes and passes everything else through. `+` stays a
+/// plus sign: it only means space in form bodies, not in URLs generally.
+pub fn percent_decode(text: &str) -> Result<Vec<u8>, SerializeError> {
+    let input = text.as_bytes();
+    let mut result = Vec::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < input.len() {
+        if input[i] != b'%' {
+            result.push(input[i]);
+            i += 1;
+            continue;
+        }
+
+        let mut value = 0;
+        for offset in i + 1..i + 3 {
+            let &c = input.get(offset).ok_or(SerializeError::TruncatedInput { offset: input.len() })?;
+            let digit = (c as char).to_digit(16).ok_or(SerializeError::InvalidCharacter { offset, byte: c })?;
+            value = value << 4 | digit as u8;
+        }
+        result.push(value);
+        i += 3;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        assert_eq!(percent_encode(b"comment1=cooking MCs;userdata=x"), "comment1%3Dcooking%20MCs%3Buserdata%3Dx");
+        assert_eq!(percent_encode("€".as_bytes()), "%E2%82%AC");
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(percent_decode(&percent_encode(&bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(percent_decode("a+b%2fc").unwrap(), b"a+b/c");
+        assert_eq!(percent_decode("%4"), Err(SerializeError::TruncatedInput { offset: 2 }));
+        assert_eq!(percent_decode("ab%g1"), Err(SerializeError::InvalidCharacter { offset: 3, byte: b'g' }));
+    }
+}
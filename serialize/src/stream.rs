@@ -0,0 +1,335 @@
+use std::io::{self, Read, Write};
+
+use crate::base64::{self, Base64Config};
+use crate::hex::{self, HexConfig};
+use crate::{to_base64_with, to_hex, to_hex_upper, SerializeError};
+
+/// How much encoded input the readers pull from the inner reader at a time
+const CHUNK: usize = 4096;
+
+fn invalid_data(e: SerializeError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// Encodes everything written to it as Base64 into the inner writer,
+/// optionally wrapped at a fixed line width (64 for PEM, 76 for MIME).
+/// The last partial group is only written by `finish`, or when dropped.
+pub struct Base64Writer<W: Write> {
+    inner: Option<W>,
+    config: Base64Config,
+    pending: Vec<u8>,
+    line_width: Option<usize>,
+    column: usize,
+}
+
+impl<W: Write> Base64Writer<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_config(inner, Base64Config::STANDARD)
+    }
+
+    pub fn with_config(inner: W, config: Base64Config) -> Self {
+        Self { inner: Some(inner), config, pending: Vec::with_capacity(3), line_width: None, column: 0 }
+    }
+
+    /// Break lines every `width` characters
+    pub fn wrap(mut self, width: usize) -> Self {
+        self.line_width = Some(width).filter(|&w| w > 0);
+        self
+    }
+
+    fn emit(&mut self, encoded: &str) -> io::Result<()> {
+        let inner = self.inner.as_mut().expect("writer used after finish");
+        let Some(width) = self.line_width else {
+            return inner.write_all(encoded.as_bytes());
+        };
+
+        let mut rest = encoded.as_bytes();
+        while !rest.is_empty() {
+            if self.column == width {
+                inner.write_all(b"\n")?;
+                self.column = 0;
+            }
+            let n = rest.len().min(width - self.column);
+            inner.write_all(&rest[..n])?;
+            self.column += n;
+            rest = &rest[n..];
+        }
+
+        Ok(())
+    }
+
+    fn flush_pending(&mut self) -> io::Result<()> {
+        if self.inner.is_some() && !self.pending.is_empty() {
+            let encoded = to_base64_with(&self.pending, &self.config);
+            self.pending.clear();
+            self.emit(&encoded)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the final group and its padding and hands back the inner writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush_pending()?;
+        let mut inner = self.inner.take().unwrap();
+        inner.flush()?;
+        Ok(inner)
+    }
+}
+
+impl<W: Write> Write for Base64Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+
+        // Encode whole groups only, so no padding lands mid-stream
+        let whole = self.pending.len() / 3 * 3;
+        if whole > 0 {
+            let encoded = to_base64_with(&self.pending[..whole], &self.config);
+            self.pending.drain(..whole);
+            self.emit(&encoded)?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.as_mut().map_or(Ok(()), |inner| inner.flush())
+    }
+}
+
+impl<W: Write> Drop for Base64Writer<W> {
+    fn drop(&mut self) {
+        let _ = self.flush_pending();
+    }
+}
+
+/// Decodes Base64 from the inner reader. Input may arrive in pieces split
+/// anywhere, including across groups and line breaks; errors come back as
+/// `InvalidData` wrapping the `SerializeError`, with offsets into the stream.
+pub struct Base64Reader<R: Read> {
+    inner: R,
+    decoder: base64::Decoder,
+    decoded: Vec<u8>,
+    position: usize,
+    done: bool,
+}
+
+impl<R: Read> Base64Reader<R> {
+    pub fn new(inner: R) -> Self {
+        Self::with_config(inner, Base64Config::STANDARD)
+    }
+
+    pub fn with_config(inner: R, config: Base64Config) -> Self {
+        Self { inner, decoder: base64::Decoder::new(config), decoded: Vec::new(), position: 0, done: false }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Base64Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut chunk = [0u8; CHUNK];
+
+        while self.position == self.decoded.len() && !self.done {
+            self.decoded.clear();
+            self.position = 0;
+
+            let n = self.inner.read(&mut chunk)?;
+            if n == 0 {
+                self.done = true;
+                self.decoder.finish(&mut self.decoded).map_err(invalid_data)?;
+            } else {
+                self.decoder.feed(&chunk[..n], &mut self.decoded).map_err(invalid_data)?;
+            }
+        }
+
+        let n = buf.len().min(self.decoded.len() - self.position);
+        buf[..n].copy_from_slice(&self.decoded[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Encodes everything written to it as hex into the inner writer
+pub struct HexWriter<W: Write> {
+    inner: W,
+    upper: bool,
+}
+
+impl<W: Write> HexWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, upper: false }
+    }
+
+    pub fn uppercase(self) -> Self {
+        Self { upper: true, ..self }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for HexWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let encoded = if self.upper { to_hex_upper(buf) } else { to_hex(buf) };
+        self.inner.write_all(encoded.as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Decodes hex from the inner reader, with the same splitting and error
+/// behaviour as `Base64Reader`
+pub struct HexReader<R: Read> {
+    inner: R,
+    decoder: hex::Decoder,
+    decoded: Vec<u8>,
+    position: usize,
+    done: bool,
+}
+
+impl<R: Read> HexReader<R> {
+    /// Bare digit pairs, which may be wrapped over lines like `Base64Reader`
+    /// input
+    pub fn new(inner: R) -> Self {
+        Self::with_config(inner, HexConfig::STRICT.with_line_breaks(true))
+    }
+
+    pub fn with_config(inner: R, config: HexConfig) -> Self {
+        Self { inner, decoder: hex::Decoder::new(config), decoded: Vec::new(), position: 0, done: false }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for HexReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut chunk = [0u8; CHUNK];
+
+        while self.position == self.decoded.len() && !self.done {
+            self.decoded.clear();
+            self.position = 0;
+
+            let n = self.inner.read(&mut chunk)?;
+            if n == 0 {
+                self.done = true;
+                self.decoder.finish().map_err(invalid_data)?;
+            } else {
+                let decoded = &mut self.decoded;
+                self.decoder.feed(&chunk[..n], |b| {
+                    decoded.push(b);
+                    Ok(())
+                }).map_err(invalid_data)?;
+            }
+        }
+
+        let n = buf.len().min(self.decoded.len() - self.position);
+        buf[..n].copy_from_slice(&self.decoded[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_base64;
+
+    /// Hands out at most `step` bytes per read
+    struct Trickle<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.step.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    fn sample() -> Vec<u8> {
+        (0..200u32).map(|i| (i * 7 + 3) as u8).collect()
+    }
+
+    #[test]
+    fn test_base64_writer_wraps_lines() {
+        let data = sample();
+        let mut writer = Base64Writer::new(Vec::new()).wrap(76);
+        for piece in data.chunks(5) {
+            writer.write_all(piece).unwrap();
+        }
+        let encoded = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        assert!(encoded.lines().all(|line| line.len() <= 76));
+        assert_eq!(encoded.replace('\n', ""), to_base64(&data));
+    }
+
+    #[test]
+    fn test_base64_reader_split_input() {
+        let data = sample();
+        let mut wrapped = Base64Writer::new(Vec::new()).wrap(60);
+        wrapped.write_all(&data).unwrap();
+        let encoded = wrapped.finish().unwrap();
+
+        for step in [1, 3, 7, 61] {
+            let mut decoded = Vec::new();
+            Base64Reader::new(Trickle { data: &encoded, step }).read_to_end(&mut decoded).unwrap();
+            assert_eq!(decoded, data);
+        }
+    }
+
+    #[test]
+    fn test_reader_errors_carry_stream_offsets() {
+        let input = b"QUJD\nQUJD\nQU*D";
+        let mut reader = Base64Reader::new(Trickle { data: input, step: 4 });
+        let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(SerializeError::from(error), SerializeError::InvalidCharacter { offset: 12, byte: b'*' });
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let data = sample();
+        let mut writer = HexWriter::new(Vec::new()).uppercase();
+        writer.write_all(&data).unwrap();
+        let encoded = writer.into_inner();
+
+        let mut decoded = Vec::new();
+        HexReader::new(Trickle { data: &encoded, step: 3 }).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, data);
+
+        let mut decoded = Vec::new();
+        let reader = HexReader::with_config(Trickle { data: b"0xde:ad be:ef", step: 1 }, HexConfig::LENIENT);
+        reader.take(16).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, [0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn test_hex_reader_wrapped_lines() {
+        let data = sample();
+        let mut encoded = Vec::new();
+        for line in to_hex(&data).as_bytes().chunks(64) {
+            encoded.extend_from_slice(line);
+            encoded.extend_from_slice(b"\r\n");
+        }
+
+        for step in [1, 2, 5, 65] {
+            let mut decoded = Vec::new();
+            HexReader::new(Trickle { data: &encoded, step }).read_to_end(&mut decoded).unwrap();
+            assert_eq!(decoded, data);
+        }
+
+        let mut reader = HexReader::new(Trickle { data: b"de\nad\nb\ne", step: 1 });
+        let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(SerializeError::from(error), SerializeError::InvalidCharacter { offset: 7, byte: b'\n' });
+    }
+}
@@ -0,0 +1,283 @@
+// timing/src/attack.rs
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+use serialize::to_hex;
+
+use crate::stats::{median, median_absolute_deviation, trimmed_mean};
+
+#[derive(Debug, Clone)]
+pub struct AttackConfig {
+    /// Length of the signature being forged
+    pub signature_len: usize,
+    /// Timing samples per candidate byte in the first pass
+    pub samples: usize,
+    /// How many of the slowest candidates get re-measured
+    pub finalists: usize,
+    /// Re-measurement rounds before settling on the current best guess
+    pub max_rounds: usize,
+    /// Fraction of samples dropped from each end for the trimmed mean
+    pub trim: f64,
+    /// How many times an earlier byte may be revisited after a bad guess
+    pub max_backtracks: usize,
+}
+
+impl Default for AttackConfig {
+    fn default() -> Self {
+        Self {
+            signature_len: 20,
+            samples: 3,
+            finalists: 6,
+            max_rounds: 6,
+            trim: 0.2,
+            max_backtracks: 4,
+        }
+    }
+}
+
+pub struct Client {
+    addr: SocketAddr,
+}
+
+impl Client {
+    pub fn new(addr: SocketAddr) -> Self {
+        Self { addr }
+    }
+
+    /// Send one request, returning whether it was accepted and how long the server took
+    pub fn probe(&self, file: &str, signature: &[u8]) -> io::Result<(bool, Duration)> {
+        let mut stream = TcpStream::connect(self.addr)?;
+        stream.set_nodelay(true)?;
+
+        let request = format!(
+            "GET /test?file={}&signature={} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+            file,
+            to_hex(signature),
+            self.addr
+        );
+
+        let start = Instant::now();
+        stream.write_all(request.as_bytes())?;
+
+        let mut status_line = String::new();
+        BufReader::new(&stream).read_line(&mut status_line)?;
+        let elapsed = start.elapsed();
+
+        let accepted = status_line.split_whitespace().nth(1) == Some("200");
+        Ok((accepted, elapsed))
+    }
+}
+
+/// Recover the server's HMAC for `file` one byte at a time from response timings.
+///
+/// Each candidate is scored by the median of a few samples, the slowest few are
+/// re-measured with a trimmed mean until one stands clear of the rest, and a
+/// guess is revisited when the next position doesn't get slower by the leak we
+/// measured. The last byte is found directly from the status code.
+pub fn recover_signature<F>(
+    client: &Client,
+    file: &str,
+    config: &AttackConfig,
+    mut on_progress: F,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>>
+where
+    F: FnMut(&[u8]),
+{
+    let len = config.signature_len;
+    let mut known: Vec<u8> = Vec::with_capacity(len);
+    let mut baselines: Vec<f64> = Vec::with_capacity(len);
+    let mut leak: Option<f64> = None;
+    let mut backtracks = 0;
+
+    while known.len() < len {
+        let position = known.len();
+        let mut guess = known.clone();
+        guess.resize(len, 0);
+
+        if position == len - 1 {
+            for candidate in 0..=255u8 {
+                guess[position] = candidate;
+                if client.probe(file, &guess)?.0 {
+                    on_progress(&guess);
+                    return Ok(guess);
+                }
+            }
+
+            if backtracks >= config.max_backtracks || known.is_empty() {
+                return Err("No candidate for the last byte was accepted".into());
+            }
+            backtracks += 1;
+            known.pop();
+            baselines.pop();
+            continue;
+        }
+
+        let mut timings: Vec<Vec<f64>> = vec![Vec::new(); 256];
+        if let Some(accepted) = sample_candidates(client, file, &mut guess, position, 0..=255, config.samples, &mut timings)? {
+            on_progress(&accepted);
+            return Ok(accepted);
+        }
+
+        let scores: Vec<f64> = timings.iter().map(|samples| median(samples)).collect();
+        let baseline = median(&scores);
+        let noise = median_absolute_deviation(&scores).max(1.0);
+
+        // A right guess one position back makes every candidate here slower
+        if let (Some(leak), Some(&previous)) = (leak, baselines.last())
+            && baseline - previous < leak / 2.0
+            && backtracks < config.max_backtracks
+        {
+            backtracks += 1;
+            known.pop();
+            baselines.pop();
+            continue;
+        }
+
+        let mut ranked: Vec<u8> = (0..=255u8).collect();
+        let mut best = ranked[0];
+        let mut settled = false;
+
+        for _ in 0..config.max_rounds {
+            let score = |c: u8| robust_score(&timings[c as usize], config.trim);
+            ranked.sort_by(|&a, &b| score(b).partial_cmp(&score(a)).unwrap_or(std::cmp::Ordering::Equal));
+            ranked.truncate(config.finalists.max(2));
+            best = ranked[0];
+
+            // A leader on one or two samples may just be a scheduling hiccup
+            let gap = score(ranked[0]) - score(ranked[1]);
+            let threshold = leak.map_or(8.0 * noise, |leak| leak / 2.0);
+            if gap > threshold && timings[best as usize].len() >= 3 {
+                // A burst of load can carry one candidate through several samples,
+                // so the leader has to beat the runner-up again side by side
+                let mut fresh: Vec<Vec<f64>> = vec![Vec::new(); 256];
+                let pair = [best, ranked[1]];
+                if let Some(accepted) = sample_candidates(client, file, &mut guess, position, pair, 3, &mut fresh)? {
+                    on_progress(&accepted);
+                    return Ok(accepted);
+                }
+                if median(&fresh[best as usize]) - median(&fresh[ranked[1] as usize]) > threshold {
+                    settled = true;
+                    break;
+                }
+                for candidate in pair {
+                    timings[candidate as usize].append(&mut fresh[candidate as usize]);
+                }
+            }
+
+            let finalists = ranked.clone();
+            if let Some(accepted) = sample_candidates(client, file, &mut guess, position, finalists, config.samples * 2, &mut timings)? {
+                on_progress(&accepted);
+                return Ok(accepted);
+            }
+        }
+
+        // No clear leader usually means the right byte fell out of the
+        // finalists on a noisy first pass, so measure this position again
+        if !settled && backtracks < config.max_backtracks {
+            backtracks += 1;
+            continue;
+        }
+
+        if leak.is_none() {
+            leak = Some(robust_score(&timings[best as usize], config.trim) - baseline);
+        }
+
+        known.push(best);
+        baselines.push(baseline);
+        on_progress(&known);
+    }
+
+    Ok(known)
+}
+
+/// Trimmed mean once there are enough samples for trimming to drop any,
+/// median before that
+fn robust_score(samples: &[f64], trim: f64) -> f64 {
+    if samples.len() < 5 {
+        median(samples)
+    } else {
+        trimmed_mean(samples, trim)
+    }
+}
+
+fn sample_candidates<I>(
+    client: &Client,
+    file: &str,
+    guess: &mut [u8],
+    position: usize,
+    candidates: I,
+    samples: usize,
+    timings: &mut [Vec<f64>],
+) -> io::Result<Option<Vec<u8>>>
+where
+    I: IntoIterator<Item = u8> + Clone,
+{
+    // Interleave candidates so drift in machine load spreads evenly across them
+    for _ in 0..samples {
+        for candidate in candidates.clone() {
+            guess[position] = candidate;
+            let (accepted, elapsed) = client.probe(file, guess)?;
+            if accepted {
+                return Ok(Some(guess.to_vec()));
+            }
+            timings[candidate as usize].push(elapsed.as_secs_f64() * 1e6);
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{Server, ServerConfig};
+    use std::sync::Mutex;
+
+    /// The attacks measure wall-clock time, so running two at once on a
+    /// small machine drowns the leak in scheduling noise
+    static TIMING: Mutex<()> = Mutex::new(());
+
+    // The attack tests take the better part of a minute each and depend on
+    // the machine staying quiet, so they are left out of the default run:
+    // `cargo test -p timing -- --ignored` runs them.
+
+    #[test]
+    #[ignore = "slow and timing-sensitive; run with --ignored"]
+    fn test_recover_truncated_signature_with_5ms_leak() {
+        let _guard = TIMING.lock().unwrap_or_else(|e| e.into_inner());
+        let config = ServerConfig::new(b"timing leak test key", Duration::from_millis(5))
+            .with_signature_len(3);
+        let expected = config.expected_signature("foo");
+
+        let server = Server::bind("127.0.0.1:0", config).unwrap();
+        let client = Client::new(server.local_addr().unwrap());
+        server.spawn();
+
+        let attack = AttackConfig { signature_len: 3, ..AttackConfig::default() };
+        let recovered = recover_signature(&client, "foo", &attack, |_| {}).unwrap();
+
+        assert_eq!(recovered, expected);
+    }
+
+    /// The full 20-byte HMAC-SHA1 through the statistical path, kept quick
+    /// with a 1ms leak and single-sample first passes. The wider field of
+    /// finalists keeps the right byte in the running when a pass is noisy.
+    #[test]
+    #[ignore = "slow and timing-sensitive; run with --ignored"]
+    fn test_recover_full_signature_with_1ms_leak() {
+        let _guard = TIMING.lock().unwrap_or_else(|e| e.into_inner());
+        let config = ServerConfig::new(b"timing leak test key", Duration::from_millis(1));
+        let expected = config.expected_signature("bar");
+
+        let server = Server::bind("127.0.0.1:0", config).unwrap();
+        let client = Client::new(server.local_addr().unwrap());
+        server.spawn();
+
+        let attack = AttackConfig { samples: 1, finalists: 24, max_backtracks: 32, ..AttackConfig::default() };
+        assert_eq!(attack.signature_len, expected.len());
+        let recovered = recover_signature(&client, "bar", &attack, |_| {}).unwrap();
+
+        assert_eq!(recovered, expected);
+    }
+}
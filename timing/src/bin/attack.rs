@@ -0,0 +1,40 @@
+use std::io::{self, Write};
+use std::net::ToSocketAddrs;
+
+use serialize::to_hex;
+use timing::cli::parse_args;
+use timing::{recover_signature, AttackConfig, Client};
+
+fn main() {
+    let args = parse_args("[--addr HOST:PORT] [--file NAME] [--signature-len N] [--samples N]");
+
+    let addr = args.get("addr").map(String::as_str).unwrap_or("127.0.0.1:9000")
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .expect("--addr must be HOST:PORT");
+    let file = args.get("file").map(String::as_str).unwrap_or("foo");
+
+    let mut config = AttackConfig::default();
+    if let Some(len) = args.get("signature-len").and_then(|v| v.parse().ok()) {
+        config.signature_len = len;
+    }
+    if let Some(samples) = args.get("samples").and_then(|v| v.parse().ok()) {
+        config.samples = samples;
+    }
+
+    let client = Client::new(addr);
+    let result = recover_signature(&client, file, &config, |known| {
+        print!("\r{}", to_hex(known));
+        io::stdout().flush().unwrap();
+    });
+    println!();
+
+    match result {
+        Ok(signature) => println!("Recovered signature: {}", to_hex(&signature)),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
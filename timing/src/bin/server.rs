@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use crypto::utils::oracle::random_aes_key;
+use serialize::from_hex;
+use timing::cli::parse_args;
+use timing::{Server, ServerConfig};
+
+fn main() {
+    let args = parse_args("[--addr HOST:PORT] [--delay-ms N] [--key HEX] [--signature-len N]");
+
+    let addr = args.get("addr").map(String::as_str).unwrap_or("127.0.0.1:9000");
+    let delay_ms: u64 = args.get("delay-ms").and_then(|v| v.parse().ok()).unwrap_or(50);
+    let key = match args.get("key") {
+        Some(hex) => from_hex(hex).expect("--key must be hex"),
+        None => random_aes_key().to_vec(),
+    };
+
+    let mut config = ServerConfig::new(&key, Duration::from_millis(delay_ms));
+    if let Some(len) = args.get("signature-len").and_then(|v| v.parse().ok()) {
+        config = config.with_signature_len(len);
+    }
+
+    let server = Server::bind(addr, config).expect("Failed to bind");
+    println!("Listening on http://{} ({}ms per byte)", server.local_addr().unwrap(), delay_ms);
+
+    if let Err(e) = server.run() {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
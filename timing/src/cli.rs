@@ -0,0 +1,18 @@
+use std::collections::HashMap;
+use std::env;
+
+/// `--name value` pairs from the command line, keyed without the dashes.
+/// Prints `usage` after the program name and exits on anything else.
+pub fn parse_args(usage: &str) -> HashMap<String, String> {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() % 2 != 1 || args[1..].chunks(2).any(|pair| !pair[0].starts_with("--")) {
+        eprintln!("Usage: {} {}", args[0], usage);
+        std::process::exit(1);
+    }
+
+    args[1..]
+        .chunks(2)
+        .map(|pair| (pair[0].trim_start_matches("--").to_string(), pair[1].clone()))
+        .collect()
+}
@@ -0,0 +1,7 @@
+pub mod attack;
+pub mod cli;
+pub mod server;
+pub mod stats;
+
+pub use attack::{recover_signature, AttackConfig, Client};
+pub use server::{insecure_compare, Server, ServerConfig};
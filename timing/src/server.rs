@@ -0,0 +1,194 @@
+// timing/src/server.rs
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crypto::hmac_sha1;
+use serialize::from_hex;
+
+const SHA1_OUTPUT_SIZE: usize = 20;
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub key: Vec<u8>,
+    /// Artificial sleep after every matching signature byte
+    pub delay: Duration,
+    /// Number of leading HMAC bytes the server checks (20 for full HMAC-SHA1)
+    pub signature_len: usize,
+}
+
+impl ServerConfig {
+    pub fn new(key: &[u8], delay: Duration) -> Self {
+        Self {
+            key: key.to_vec(),
+            delay,
+            signature_len: SHA1_OUTPUT_SIZE,
+        }
+    }
+
+    pub fn with_signature_len(mut self, signature_len: usize) -> Self {
+        self.signature_len = signature_len.clamp(1, SHA1_OUTPUT_SIZE);
+        self
+    }
+
+    /// The (possibly truncated) HMAC-SHA1 the server expects for `file`
+    pub fn expected_signature(&self, file: &str) -> Vec<u8> {
+        let mut mac = hmac_sha1(&self.key, file.as_bytes());
+        mac.truncate(self.signature_len);
+        mac
+    }
+}
+
+/// A tiny HTTP server answering `GET /test?file=...&signature=...`
+/// with 200 for a valid HMAC and 500 otherwise.
+pub struct Server {
+    listener: TcpListener,
+    config: Arc<ServerConfig>,
+}
+
+impl Server {
+    pub fn bind<A: ToSocketAddrs>(addr: A, config: ServerConfig) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            config: Arc::new(config),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serve forever, one thread per connection
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let config = Arc::clone(&self.config);
+
+            thread::spawn(move || {
+                // A client hanging up mid-request is not the server's problem
+                let _ = handle_connection(stream, &config);
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn spawn(self) -> thread::JoinHandle<io::Result<()>> {
+        thread::spawn(move || self.run())
+    }
+}
+
+/// Byte-at-a-time comparison with an early exit and a sleep per matching byte
+pub fn insecure_compare(a: &[u8], b: &[u8], delay: Duration) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    for (x, y) in a.iter().zip(b.iter()) {
+        if x != y {
+            return false;
+        }
+        thread::sleep(delay);
+    }
+
+    true
+}
+
+fn handle_connection(stream: TcpStream, config: &ServerConfig) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Drain headers up to the blank line
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+
+    let (status, reason) = match parse_request(&request_line) {
+        Some((file, signature)) => {
+            let expected = config.expected_signature(&file);
+            if insecure_compare(&signature, &expected, config.delay) {
+                (200, "OK")
+            } else {
+                (500, "Internal Server Error")
+            }
+        }
+        None => (400, "Bad Request"),
+    };
+
+    let mut stream = stream;
+    write!(stream, "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status, reason)?;
+    stream.flush()
+}
+
+fn parse_request(request_line: &str) -> Option<(String, Vec<u8>)> {
+    let mut parts = request_line.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+
+    let target = parts.next()?;
+    let (path, query) = target.split_once('?')?;
+    if path != "/test" {
+        return None;
+    }
+
+    let mut file = None;
+    let mut signature = None;
+
+    for pair in query.split('&') {
+        match pair.split_once('=') {
+            Some(("file", value)) => file = Some(value.to_string()),
+            Some(("signature", value)) => signature = from_hex(value).ok(),
+            _ => {}
+        }
+    }
+
+    Some((file?, signature?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serialize::to_hex;
+
+    #[test]
+    fn test_parse_request() {
+        let (file, signature) = parse_request("GET /test?file=foo&signature=46b4ec58 HTTP/1.1\r\n").unwrap();
+        assert_eq!(file, "foo");
+        assert_eq!(signature, vec![0x46, 0xb4, 0xec, 0x58]);
+
+        assert!(parse_request("GET /test?file=foo HTTP/1.1\r\n").is_none());
+        assert!(parse_request("POST /test?file=foo&signature=00 HTTP/1.1\r\n").is_none());
+    }
+
+    /// The status line the server answers `request_line` with
+    fn status(addr: SocketAddr, request_line: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "{}\r\nConnection: close\r\n\r\n", request_line).unwrap();
+
+        let mut status = String::new();
+        BufReader::new(stream).read_line(&mut status).unwrap();
+        status.trim_end().to_string()
+    }
+
+    #[test]
+    fn test_server_checks_signature() {
+        let config = ServerConfig::new(b"server test key", Duration::ZERO).with_signature_len(4);
+        let valid = to_hex(&config.expected_signature("foo"));
+
+        let server = Server::bind("127.0.0.1:0", config).unwrap();
+        let addr = server.local_addr().unwrap();
+        server.spawn();
+
+        let request = format!("GET /test?file=foo&signature={} HTTP/1.1", valid);
+        assert_eq!(status(addr, &request), "HTTP/1.1 200 OK");
+        let request = format!("GET /test?file=bar&signature={} HTTP/1.1", valid);
+        assert_eq!(status(addr, &request), "HTTP/1.1 500 Internal Server Error");
+        assert_eq!(status(addr, "GET /test?file=foo HTTP/1.1"), "HTTP/1.1 400 Bad Request");
+    }
+}
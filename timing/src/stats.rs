@@ -0,0 +1,57 @@
+// timing/src/stats.rs
+
+/// Median of the samples, or 0.0 for an empty slice
+pub fn median(samples: &[f64]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+
+    let sorted = sorted(samples);
+    let mid = sorted.len() / 2;
+
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Mean after discarding `trim` (0.0..0.5) of the samples from each end
+pub fn trimmed_mean(samples: &[f64], trim: f64) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+
+    let sorted = sorted(samples);
+    let cut = ((sorted.len() as f64) * trim.clamp(0.0, 0.49)) as usize;
+    let kept = &sorted[cut..sorted.len() - cut];
+
+    kept.iter().sum::<f64>() / kept.len() as f64
+}
+
+/// Median absolute deviation, a spread estimate that ignores outliers
+pub fn median_absolute_deviation(samples: &[f64]) -> f64 {
+    let center = median(samples);
+    let deviations: Vec<f64> = samples.iter().map(|s| (s - center).abs()).collect();
+    median(&deviations)
+}
+
+fn sorted(samples: &[f64]) -> Vec<f64> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_robust_statistics_ignore_outliers() {
+        let samples = [10.0, 11.0, 9.0, 10.0, 500.0];
+
+        assert_eq!(median(&samples), 10.0);
+        assert_eq!(trimmed_mean(&samples, 0.2), 31.0 / 3.0);
+        assert_eq!(median_absolute_deviation(&samples), 1.0);
+    }
+}
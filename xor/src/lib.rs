@@ -36,4 +36,43 @@ pub fn repeating_key_xor(data: &[u8], key: &[u8]) -> Vec<u8> {
         .zip(key.iter().cycle())
         .map(|(data_byte, key_byte)| data_byte ^ key_byte)
         .collect()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_length_mismatch() {
+        assert_eq!(fixed_xor(b"ab", b"a"), Err(XorError::LengthMismatch { left: 2, right: 1 }));
+        assert_eq!(repeating_key_xor(b"ab", b""), b"ab");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_fixed_xor_involution((a, b) in (0..64usize).prop_flat_map(|len| {
+            let bytes = prop::collection::vec(any::<u8>(), len);
+            (bytes.clone(), bytes)
+        })) {
+            let once = fixed_xor(&a, &b).unwrap();
+            prop_assert!(fixed_xor(&a, &a).unwrap().iter().all(|&x| x == 0));
+            prop_assert_eq!(fixed_xor(&once, &b).unwrap(), a);
+        }
+
+        #[test]
+        fn prop_single_byte_xor_involution(data in prop::collection::vec(any::<u8>(), 0..64), key: u8) {
+            prop_assert_eq!(single_byte_xor(&single_byte_xor(&data, key), key), data);
+        }
+
+        #[test]
+        fn prop_repeating_key_xor_involution(
+            data in prop::collection::vec(any::<u8>(), 0..128),
+            key in prop::collection::vec(any::<u8>(), 0..16),
+        ) {
+            let once = repeating_key_xor(&data, &key);
+            prop_assert_eq!(once.len(), data.len());
+            prop_assert_eq!(repeating_key_xor(&once, &key), data);
+        }
+    }
+}